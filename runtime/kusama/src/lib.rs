@@ -59,7 +59,7 @@ use xcm_builder::{
 	AccountId32Aliases, ChildParachainConvertsVia, SovereignSignedViaLocation, CurrencyAdapter as XcmCurrencyAdapter,
 	ChildParachainAsNative, SignedAccountId32AsNative, ChildSystemParachainAsSuperuser, LocationInverter,
 	IsConcrete, FixedWeightBounds, TakeWeightCredit, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom,
-	IsChildSystemParachain, UsingComponents, BackingToPlurality, SignedToAccountId32,
+	IsChildSystemParachain, UsingComponents, BackingToPlurality, SignedToAccountId32, SystemParachains,
 };
 use xcm_executor::XcmExecutor;
 use sp_arithmetic::Perquintill;
@@ -1217,7 +1217,7 @@ type LocalOriginConverter = (
 	// The AccountId32 location type can be expressed natively as a `Signed` origin.
 	SignedAccountId32AsNative<KusamaNetwork, Origin>,
 	// A system child parachain, expressed as a Superuser, converts to the `Root` origin.
-	ChildSystemParachainAsSuperuser<ParaId, Origin>,
+	ChildSystemParachainAsSuperuser<SystemParachains, Origin>,
 );
 
 parameter_types! {
@@ -1247,7 +1247,7 @@ pub type Barrier = (
 	// If the message is one that immediately attemps to pay for execution, then allow it.
 	AllowTopLevelPaidExecutionFrom<All<MultiLocation>>,
 	// Messages coming from system parachains need not pay for execution.
-	AllowUnpaidExecutionFrom<IsChildSystemParachain<ParaId>>,
+	AllowUnpaidExecutionFrom<IsChildSystemParachain<SystemParachains>>,
 );
 
 pub struct XcmConfig;
@@ -1264,6 +1264,7 @@ impl xcm_executor::Config for XcmConfig {
 	// The weight trader piggybacks on the existing transaction-fee conversion logic.
 	type Trader = UsingComponents<WeightToFee, KsmLocation, AccountId, Balances, ToAuthor<Runtime>>;
 	type ResponseHandler = ();
+	type AssetExchanger = ();
 }
 
 parameter_types! {
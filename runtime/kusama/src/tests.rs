@@ -168,3 +168,57 @@ fn era_payout_should_give_sensible_results() {
 		0,
 	), (6, 4));
 }
+
+/// Calls into the XCM pallet, each of which moves value out of the caller's account without being a `Balances` call.
+fn xcm_pallet_calls() -> Vec<Call> {
+	use xcm::v0::{Junction::AccountId32, MultiAsset::ConcreteFungible};
+
+	let dest: MultiLocation = X1(Parachain(1000));
+	let beneficiary: MultiLocation = X1(AccountId32 { network: NetworkId::Kusama, id: [1u8; 32] });
+	let assets = vec![ConcreteFungible { id: Null, amount: 100 }];
+	vec![
+		Call::XcmPallet(pallet_xcm::Call::send(
+			dest.clone(),
+			Xcm::TransferAsset { assets: assets.clone(), dest: beneficiary.clone() },
+		)),
+		Call::XcmPallet(pallet_xcm::Call::teleport_assets(
+			dest.clone(), beneficiary.clone(), assets.clone(), 1_000_000_000,
+		)),
+		Call::XcmPallet(pallet_xcm::Call::reserve_transfer_assets(dest, beneficiary, assets, 1_000_000_000)),
+	]
+}
+
+#[test]
+fn only_any_proxy_can_dispatch_xcm_pallet_calls() {
+	let restricted = [
+		ProxyType::NonTransfer,
+		ProxyType::Governance,
+		ProxyType::Staking,
+		ProxyType::IdentityJudgement,
+		ProxyType::CancelProxy,
+	];
+	for call in xcm_pallet_calls().iter() {
+		assert!(ProxyType::Any.filter(call));
+		for proxy_type in restricted.iter() {
+			assert!(!proxy_type.filter(call), "{:?} must be filtered for {:?}", call, proxy_type);
+		}
+	}
+}
+
+#[test]
+fn non_transfer_proxy_filters_xcm_alongside_other_value_transfers() {
+	// `NonTransfer` is a superset of every other restricted type, so it must not let XCM through on their behalf.
+	assert!(ProxyType::NonTransfer.is_superset(&ProxyType::Governance));
+	assert!(ProxyType::NonTransfer.is_superset(&ProxyType::Staking));
+
+	// Kusama-specific calls which `NonTransfer` allows, next to the XCM calls and registrar `swap` which it does not.
+	assert!(ProxyType::NonTransfer.filter(&Call::Registrar(paras_registrar::Call::reserve())));
+	assert!(ProxyType::NonTransfer.filter(&Call::System(frame_system::Call::remark(vec![1, 2, 3]))));
+	assert!(!ProxyType::NonTransfer.filter(&Call::Registrar(paras_registrar::Call::swap(1.into(), 2.into()))));
+
+	// A `Governance` proxy may spend from the treasury through its own pallet but not move funds over XCM.
+	assert!(ProxyType::Governance.filter(&Call::Treasury(pallet_treasury::Call::reject_proposal(0))));
+	for call in xcm_pallet_calls().iter() {
+		assert!(!ProxyType::Governance.filter(call));
+	}
+}
@@ -61,12 +61,17 @@ impl UmpSink for () {
 pub struct XcmSink<XcmExecutor, Call>(PhantomData<(XcmExecutor, Call)>);
 
 impl<XcmExecutor: xcm::v0::ExecuteXcm<Call>, Call> UmpSink for XcmSink<XcmExecutor, Call> {
-	fn process_upward_message(origin: ParaId, mut msg: &[u8], max_weight: Weight) -> Option<Weight> {
-		use parity_scale_codec::Decode;
-		use xcm::VersionedXcm;
+	fn process_upward_message(origin: ParaId, msg: &[u8], max_weight: Weight) -> Option<Weight> {
+		use xcm::{VersionedXcm, decode_xcm_with_limit};
 		use xcm::v0::{Junction, MultiLocation, Outcome, Error as XcmError};
 
-		if let Ok(versioned_xcm_message) = VersionedXcm::decode(&mut msg) {
+		if let Ok(versioned_xcm_message) = decode_xcm_with_limit::<Call>(msg) {
+			log::trace!(
+				target: LOG_TARGET,
+				"Executing upward XCM {:?} from {:?}.",
+				xcm::xcm_hash(msg),
+				origin,
+			);
 			match versioned_xcm_message {
 				VersionedXcm::V0(xcm_message) => {
 					let xcm_junction: Junction = Junction::Parachain(origin.into());
@@ -760,6 +765,32 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn xcm_sink_rejects_deeply_nested_message() {
+		use parity_scale_codec::Encode;
+		use xcm::VersionedXcm;
+		use xcm::v0::{ExecuteXcm, MultiLocation, Outcome, Xcm};
+
+		struct PanickingExecutor;
+		impl ExecuteXcm<()> for PanickingExecutor {
+			fn execute_xcm_in_credit(_: MultiLocation, _: Xcm<()>, _: Weight, _: Weight) -> Outcome {
+				panic!("a message nested beyond the decode limit must never reach the executor");
+			}
+		}
+
+		let mut message = Xcm::<()>::WithdrawAsset { assets: vec![], effects: vec![] };
+		for _ in 0..64 {
+			message = Xcm::RelayedFrom { who: MultiLocation::Null, message: Box::new(message) };
+		}
+		let encoded = VersionedXcm::V0(message).encode();
+
+		assert!(xcm::decode_xcm_with_limit::<()>(&encoded).is_err());
+		assert_eq!(
+			XcmSink::<PanickingExecutor, ()>::process_upward_message(ParaId::from(1000), &encoded, 1000),
+			None,
+		);
+	}
+
 	#[test]
 	fn dispatch_empty() {
 		new_test_ext(default_genesis_config()).execute_with(|| {
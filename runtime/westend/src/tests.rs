@@ -36,3 +36,36 @@ fn sample_size_is_sensible() {
 	assert!(max_weight * 2 < BlockWeights::get().max_block);
 	assert!(<Runtime as auctions::Config>::WeightInfo::on_initialize() * 2 < BlockWeights::get().max_block);
 }
+
+#[test]
+fn sudo_balances_proxy_cannot_reach_xcm_pallet_through_sudo() {
+	use xcm::v0::{Junction::AccountId32, MultiAsset::ConcreteFungible};
+
+	let beneficiary: MultiLocation = X1(AccountId32 { network: NetworkId::Named(b"westend".to_vec()), id: [1u8; 32] });
+	let assets = vec![ConcreteFungible { id: Null, amount: 100 }];
+	let teleport = Call::XcmPallet(pallet_xcm::Call::teleport_assets(
+		X1(Parachain(1000)), beneficiary.clone(), assets.clone(), 1_000_000_000,
+	));
+	let reserve_transfer = Call::XcmPallet(pallet_xcm::Call::reserve_transfer_assets(
+		X1(Parachain(1000)), beneficiary.clone(), assets, 1_000_000_000,
+	));
+	let balances_transfer = Call::Balances(pallet_balances::Call::transfer(
+		Address::Id(AccountId::from([2u8; 32])), 100,
+	));
+
+	// `SudoBalances` only unwraps `sudo` for `Balances` calls; XCM transfers, wrapped or not, are rejected.
+	let sudo = |call: &Call| Call::Sudo(pallet_sudo::Call::sudo(Box::new(call.clone())));
+	assert!(ProxyType::SudoBalances.filter(&sudo(&balances_transfer)));
+	for call in [teleport, reserve_transfer].iter() {
+		assert!(!ProxyType::SudoBalances.filter(call));
+		assert!(!ProxyType::SudoBalances.filter(&sudo(call)));
+		// `NonTransfer` omits both the XCM and the Sudo pallets.
+		assert!(!ProxyType::NonTransfer.filter(call));
+		assert!(!ProxyType::NonTransfer.filter(&sudo(call)));
+		assert!(!ProxyType::Staking.filter(call));
+		assert!(ProxyType::Any.filter(call));
+	}
+
+	// `NonTransfer` does not cover `sudo` at all, even for the `Balances` calls that `SudoBalances` may make.
+	assert!(!ProxyType::NonTransfer.filter(&sudo(&balances_transfer)));
+}
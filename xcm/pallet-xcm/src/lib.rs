@@ -20,7 +20,7 @@
 
 use sp_std::{prelude::*, marker::PhantomData, convert::TryInto, boxed::Box, vec};
 use codec::{Encode, Decode};
-use xcm::v0::prelude::*;
+use xcm::{XcmHash, xcm_hash_v0, v0::prelude::*};
 use xcm_executor::traits::ConvertOrigin;
 use sp_runtime::{RuntimeDebug, traits::BadOrigin};
 use frame_support::traits::{EnsureOrigin, OriginTrait, Filter, Get, Contains};
@@ -77,8 +77,14 @@ pub mod pallet {
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		Attempted(xcm::v0::Outcome),
-		Sent(MultiLocation, MultiLocation, Xcm<()>),
+		/// A locally executed message had the given outcome. \[ outcome, hash \]
+		///
+		/// The hash is the `XcmHash` of the executed message, as given to the executor's `XcmContext`.
+		Attempted(xcm::v0::Outcome, XcmHash),
+		/// A message was sent. \[ origin, destination, message, hash \]
+		///
+		/// The hash is the `XcmHash` of the message as routed, so it matches the hash computed by the destination.
+		Sent(MultiLocation, MultiLocation, Xcm<()>, XcmHash),
 	}
 
 	#[pallet::error]
@@ -99,12 +105,12 @@ pub mod pallet {
 		#[pallet::weight(100_000_000)]
 		fn send(origin: OriginFor<T>, dest: MultiLocation, message: Xcm<()>) -> DispatchResult {
 			let origin_location = T::SendXcmOrigin::ensure_origin(origin)?;
-			Self::send_xcm(origin_location.clone(), dest.clone(), message.clone())
+			let hash = Self::send_xcm(origin_location.clone(), dest.clone(), message.clone())
 				.map_err(|e| match e {
 					XcmError::CannotReachDestination(..) => Error::<T>::Unreachable,
 					_ => Error::<T>::SendFailure,
 				})?;
-			Self::deposit_event(Event::Sent(origin_location, dest, message));
+			Self::deposit_event(Event::Sent(origin_location, dest, message, hash));
 			Ok(())
 		}
 
@@ -163,8 +169,9 @@ pub mod pallet {
 			};
 			let weight = T::Weigher::weight(&mut message)
 				.map_err(|()| Error::<T>::UnweighableMessage)?;
+			let hash = xcm_hash_v0(&message);
 			let outcome = T::XcmExecutor::execute_xcm_in_credit(origin_location, message, weight, weight);
-			Self::deposit_event(Event::Attempted(outcome));
+			Self::deposit_event(Event::Attempted(outcome, hash));
 			Ok(())
 		}
 
@@ -216,8 +223,9 @@ pub mod pallet {
 			};
 			let weight = T::Weigher::weight(&mut message)
 				.map_err(|()| Error::<T>::UnweighableMessage)?;
+			let hash = xcm_hash_v0(&message);
 			let outcome = T::XcmExecutor::execute_xcm_in_credit(origin_location, message, weight, weight);
-			Self::deposit_event(Event::Attempted(outcome));
+			Self::deposit_event(Event::Attempted(outcome, hash));
 			Ok(())
 		}
 
@@ -240,8 +248,9 @@ pub mod pallet {
 			let value = (origin_location, *message);
 			ensure!(T::XcmExecuteFilter::contains(&value), Error::<T>::Filtered);
 			let (origin_location, message) = value;
+			let hash = xcm_hash_v0(&message);
 			let outcome = T::XcmExecutor::execute_xcm(origin_location, message, max_weight);
-			Self::deposit_event(Event::Attempted(outcome));
+			Self::deposit_event(Event::Attempted(outcome, hash));
 			Ok(())
 		}
 	}
@@ -249,12 +258,16 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// Relay an XCM `message` from a given `interior` location in this context to a given `dest`
 		/// location. A null `dest` is not handled.
-		pub fn send_xcm(interior: MultiLocation, dest: MultiLocation, message: Xcm<()>) -> Result<(), XcmError> {
+		///
+		/// Returns the `XcmHash` of the message as routed.
+		pub fn send_xcm(interior: MultiLocation, dest: MultiLocation, message: Xcm<()>) -> Result<XcmHash, XcmError> {
 			let message = match interior {
 				MultiLocation::Null => message,
 				who => Xcm::<()>::RelayedFrom { who, message: Box::new(message) },
 			};
-			T::XcmRouter::send_xcm(dest, message)
+			let hash = xcm_hash_v0(&message);
+			T::XcmRouter::send_xcm(dest, message)?;
+			Ok(hash)
 		}
 
 		pub fn check_account() -> T::AccountId {
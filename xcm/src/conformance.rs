@@ -0,0 +1,356 @@
+// Copyright 2021 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Wire-format conformance tests.
+//!
+//! `GOLDEN` holds the SCALE encoding of every value in `samples`, as produced by a known-good revision of this
+//! crate. Chains running different revisions must agree on these bytes, so a failure here means that a change
+//! has altered the wire format and will break decoding between peers.
+//!
+//! If such a change is intended, regenerate the corpus with
+//! `cargo test -p xcm regenerate_golden_corpus -- --ignored --nocapture` and replace `GOLDEN` with the output.
+
+use alloc::{boxed::Box, vec, vec::Vec};
+use parity_scale_codec::{Encode, Decode};
+use crate::{VersionedXcm, VersionedMultiLocation, VersionedMultiAsset};
+use crate::v0::{
+	AssetInstance, BodyId, BodyPart, Junction, MultiAsset, MultiLocation, NetworkId, Order, OriginKind, Response,
+	Xcm,
+};
+
+#[derive(Debug, PartialEq)]
+enum Sample {
+	Location(VersionedMultiLocation),
+	Asset(VersionedMultiAsset),
+	Xcm(VersionedXcm<()>),
+}
+
+impl Sample {
+	fn encode(&self) -> Vec<u8> {
+		match self {
+			Sample::Location(x) => x.encode(),
+			Sample::Asset(x) => x.encode(),
+			Sample::Xcm(x) => x.encode(),
+		}
+	}
+
+	/// Decode `data` as the same type as `self`, requiring all of `data` to be consumed.
+	fn decode_like(&self, mut data: &[u8]) -> Result<Sample, parity_scale_codec::Error> {
+		let input = &mut data;
+		let decoded = match self {
+			Sample::Location(_) => Sample::Location(Decode::decode(input)?),
+			Sample::Asset(_) => Sample::Asset(Decode::decode(input)?),
+			Sample::Xcm(_) => Sample::Xcm(Decode::decode(input)?),
+		};
+		if !input.is_empty() {
+			return Err("trailing bytes after decoding golden blob".into());
+		}
+		Ok(decoded)
+	}
+}
+
+fn location(l: MultiLocation) -> Sample {
+	Sample::Location(VersionedMultiLocation::V0(l))
+}
+
+fn asset(a: MultiAsset) -> Sample {
+	Sample::Asset(VersionedMultiAsset::V0(a))
+}
+
+fn xcm(x: Xcm<()>) -> Sample {
+	Sample::Xcm(VersionedXcm::V0(x))
+}
+
+fn samples() -> Vec<(&'static str, Sample)> {
+	use Junction::*;
+	use MultiLocation::*;
+	let para = X2(Parent, Parachain(1000));
+	let alice = X1(AccountId32 { network: NetworkId::Any, id: [1; 32] });
+	let dot = MultiAsset::ConcreteFungible { id: X1(Parent), amount: 10_000_000_000 };
+	let buy = Order::<()>::BuyExecution {
+		fees: MultiAsset::All,
+		weight: 0,
+		debt: 3_000,
+		halt_on_error: false,
+		xcm: vec![],
+	};
+	let deposit = Order::<()>::DepositAsset { assets: vec![MultiAsset::All], dest: alice.clone() };
+	vec![
+		("location_null", location(Null)),
+		("location_parent", location(X1(Parent))),
+		("location_sibling_para", location(para.clone())),
+		("location_account_id32", location(alice.clone())),
+		("location_account_index64", location(X1(AccountIndex64 { network: NetworkId::Kusama, index: 42 }))),
+		("location_account_key20", location(X1(AccountKey20 { network: NetworkId::Named(b"eth".to_vec()), key: [2; 20] }))),
+		("location_pallet_general_index", location(X3(Parachain(2000), PalletInstance(50), GeneralIndex { id: 1_000_000 }))),
+		("location_general_key", location(X2(OnlyChild, GeneralKey(b"key".to_vec())))),
+		("location_plurality", location(X2(
+			Plurality { id: BodyId::Index { id: 3 }, part: BodyPart::Fraction { nom: 2, denom: 3 } },
+			Plurality { id: BodyId::Technical, part: BodyPart::Voice },
+		))),
+		("location_x8", location(X8(Parent, Parent, Parachain(1), Parachain(2), PalletInstance(3), OnlyChild,
+			GeneralIndex { id: 4 }, AccountIndex64 { network: NetworkId::Polkadot, index: 5 }))),
+		("asset_none", asset(MultiAsset::None)),
+		("asset_all", asset(MultiAsset::All)),
+		("asset_all_fungible", asset(MultiAsset::AllFungible)),
+		("asset_all_non_fungible", asset(MultiAsset::AllNonFungible)),
+		("asset_all_abstract_fungible", asset(MultiAsset::AllAbstractFungible { id: b"DOT".to_vec() })),
+		("asset_all_abstract_non_fungible", asset(MultiAsset::AllAbstractNonFungible { class: b"NFT".to_vec() })),
+		("asset_all_concrete_fungible", asset(MultiAsset::AllConcreteFungible { id: X1(Parent) })),
+		("asset_all_concrete_non_fungible", asset(MultiAsset::AllConcreteNonFungible { class: X1(PalletInstance(9)) })),
+		("asset_abstract_fungible", asset(MultiAsset::AbstractFungible { id: b"DOT".to_vec(), amount: u128::MAX })),
+		("asset_abstract_non_fungible", asset(MultiAsset::AbstractNonFungible {
+			class: b"NFT".to_vec(),
+			instance: AssetInstance::Array4([1, 2, 3, 4]),
+		})),
+		("asset_concrete_fungible", asset(dot.clone())),
+		("asset_concrete_non_fungible", asset(MultiAsset::ConcreteNonFungible {
+			class: X1(GeneralIndex { id: 7 }),
+			instance: AssetInstance::Index { id: 99 },
+		})),
+		("asset_instance_blob", asset(MultiAsset::ConcreteNonFungible {
+			class: Null,
+			instance: AssetInstance::Blob(vec![0xde, 0xad, 0xbe, 0xef]),
+		})),
+		("xcm_withdraw_asset", xcm(Xcm::WithdrawAsset {
+			assets: vec![dot.clone()],
+			effects: vec![buy.clone(), deposit.clone()],
+		})),
+		("xcm_reserve_asset_deposit", xcm(Xcm::ReserveAssetDeposit {
+			assets: vec![dot.clone()],
+			effects: vec![Order::Null, Order::DepositReserveAsset {
+				assets: vec![MultiAsset::AllFungible],
+				dest: para.clone(),
+				effects: vec![deposit.clone()],
+			}],
+		})),
+		("xcm_teleport_asset", xcm(Xcm::TeleportAsset {
+			assets: vec![dot.clone()],
+			effects: vec![
+				Order::ExchangeAsset { give: vec![MultiAsset::All], receive: vec![dot.clone()] },
+				Order::InitiateReserveWithdraw { assets: vec![MultiAsset::All], reserve: X1(Parent), effects: vec![] },
+				Order::InitiateTeleport { assets: vec![MultiAsset::All], dest: para.clone(), effects: vec![buy.clone()] },
+				Order::QueryHolding { query_id: 7, dest: X1(Parent), assets: vec![MultiAsset::All] },
+			],
+		})),
+		("xcm_query_response", xcm(Xcm::QueryResponse { query_id: 1, response: Response::Assets(vec![dot.clone()]) })),
+		("xcm_transfer_asset", xcm(Xcm::TransferAsset { assets: vec![dot.clone()], dest: alice.clone() })),
+		("xcm_transfer_reserve_asset", xcm(Xcm::TransferReserveAsset {
+			assets: vec![dot.clone()],
+			dest: para.clone(),
+			effects: vec![buy.clone(), deposit.clone()],
+		})),
+		("xcm_transact", xcm(Xcm::Transact {
+			origin_type: OriginKind::SovereignAccount,
+			require_weight_at_most: 1_000_000_000,
+			call: vec![0, 1, 2, 3].into(),
+		})),
+		("xcm_hrmp_new_channel_open_request", xcm(Xcm::HrmpNewChannelOpenRequest {
+			sender: 1000,
+			max_message_size: 1024,
+			max_capacity: 8,
+		})),
+		("xcm_hrmp_channel_accepted", xcm(Xcm::HrmpChannelAccepted { recipient: 2000 })),
+		("xcm_hrmp_channel_closing", xcm(Xcm::HrmpChannelClosing { initiator: 1000, sender: 1000, recipient: 2000 })),
+		("xcm_relayed_from", xcm(Xcm::RelayedFrom {
+			who: alice.clone(),
+			message: Box::new(Xcm::WithdrawAsset {
+				assets: vec![dot],
+				effects: vec![Order::BuyExecution {
+					fees: MultiAsset::All,
+					weight: 10,
+					debt: 20,
+					halt_on_error: true,
+					xcm: vec![Xcm::Transact {
+						origin_type: OriginKind::Xcm,
+						require_weight_at_most: 5,
+						call: vec![9].into(),
+					}],
+				}],
+			}),
+		})),
+	]
+}
+
+const GOLDEN: &[(&str, &[u8])] = &[
+	("location_null", &[
+		0x00, 0x00,
+	]),
+	("location_parent", &[
+		0x00, 0x01, 0x00,
+	]),
+	("location_sibling_para", &[
+		0x00, 0x02, 0x00, 0x01, 0xa1, 0x0f,
+	]),
+	("location_account_id32", &[
+		0x00, 0x01, 0x02, 0x00, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+		0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+		0x01, 0x01, 0x01, 0x01,
+	]),
+	("location_account_index64", &[
+		0x00, 0x01, 0x03, 0x03, 0xa8,
+	]),
+	("location_account_key20", &[
+		0x00, 0x01, 0x04, 0x01, 0x0c, 0x65, 0x74, 0x68, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02,
+		0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02, 0x02,
+	]),
+	("location_pallet_general_index", &[
+		0x00, 0x03, 0x01, 0x41, 0x1f, 0x05, 0x32, 0x06, 0x02, 0x09, 0x3d, 0x00,
+	]),
+	("location_general_key", &[
+		0x00, 0x02, 0x08, 0x07, 0x0c, 0x6b, 0x65, 0x79,
+	]),
+	("location_plurality", &[
+		0x00, 0x02, 0x09, 0x02, 0x0c, 0x02, 0x08, 0x0c, 0x09, 0x04, 0x00,
+	]),
+	("location_x8", &[
+		0x00, 0x08, 0x00, 0x00, 0x01, 0x04, 0x01, 0x08, 0x05, 0x03, 0x08, 0x06, 0x10, 0x03, 0x02, 0x14,
+	]),
+	("asset_none", &[
+		0x00, 0x00,
+	]),
+	("asset_all", &[
+		0x00, 0x01,
+	]),
+	("asset_all_fungible", &[
+		0x00, 0x02,
+	]),
+	("asset_all_non_fungible", &[
+		0x00, 0x03,
+	]),
+	("asset_all_abstract_fungible", &[
+		0x00, 0x04, 0x0c, 0x44, 0x4f, 0x54,
+	]),
+	("asset_all_abstract_non_fungible", &[
+		0x00, 0x05, 0x0c, 0x4e, 0x46, 0x54,
+	]),
+	("asset_all_concrete_fungible", &[
+		0x00, 0x06, 0x01, 0x00,
+	]),
+	("asset_all_concrete_non_fungible", &[
+		0x00, 0x07, 0x01, 0x05, 0x09,
+	]),
+	("asset_abstract_fungible", &[
+		0x00, 0x08, 0x0c, 0x44, 0x4f, 0x54, 0x33, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+		0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+	]),
+	("asset_abstract_non_fungible", &[
+		0x00, 0x09, 0x0c, 0x4e, 0x46, 0x54, 0x02, 0x01, 0x02, 0x03, 0x04,
+	]),
+	("asset_concrete_fungible", &[
+		0x00, 0x0a, 0x01, 0x00, 0x07, 0x00, 0xe4, 0x0b, 0x54, 0x02,
+	]),
+	("asset_concrete_non_fungible", &[
+		0x00, 0x0b, 0x01, 0x06, 0x1c, 0x01, 0x8d, 0x01,
+	]),
+	("asset_instance_blob", &[
+		0x00, 0x0b, 0x00, 0x06, 0x10, 0xde, 0xad, 0xbe, 0xef,
+	]),
+	("xcm_withdraw_asset", &[
+		0x00, 0x00, 0x04, 0x0a, 0x01, 0x00, 0x07, 0x00, 0xe4, 0x0b, 0x54, 0x02, 0x08, 0x07, 0x01, 0x00,
+		0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb8, 0x0b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+		0x00, 0x01, 0x04, 0x01, 0x01, 0x02, 0x00, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+		0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+		0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+	]),
+	("xcm_reserve_asset_deposit", &[
+		0x00, 0x01, 0x04, 0x0a, 0x01, 0x00, 0x07, 0x00, 0xe4, 0x0b, 0x54, 0x02, 0x08, 0x00, 0x02, 0x04,
+		0x02, 0x02, 0x00, 0x01, 0xa1, 0x0f, 0x04, 0x01, 0x04, 0x01, 0x01, 0x02, 0x00, 0x01, 0x01, 0x01,
+		0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+		0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+	]),
+	("xcm_teleport_asset", &[
+		0x00, 0x02, 0x04, 0x0a, 0x01, 0x00, 0x07, 0x00, 0xe4, 0x0b, 0x54, 0x02, 0x10, 0x03, 0x04, 0x01,
+		0x04, 0x0a, 0x01, 0x00, 0x07, 0x00, 0xe4, 0x0b, 0x54, 0x02, 0x04, 0x04, 0x01, 0x01, 0x00, 0x00,
+		0x05, 0x04, 0x01, 0x02, 0x00, 0x01, 0xa1, 0x0f, 0x04, 0x07, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+		0x00, 0x00, 0x00, 0xb8, 0x0b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x06, 0x1c, 0x01,
+		0x00, 0x04, 0x01,
+	]),
+	("xcm_query_response", &[
+		0x00, 0x03, 0x04, 0x00, 0x04, 0x0a, 0x01, 0x00, 0x07, 0x00, 0xe4, 0x0b, 0x54, 0x02,
+	]),
+	("xcm_transfer_asset", &[
+		0x00, 0x04, 0x04, 0x0a, 0x01, 0x00, 0x07, 0x00, 0xe4, 0x0b, 0x54, 0x02, 0x01, 0x02, 0x00, 0x01,
+		0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+		0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+	]),
+	("xcm_transfer_reserve_asset", &[
+		0x00, 0x05, 0x04, 0x0a, 0x01, 0x00, 0x07, 0x00, 0xe4, 0x0b, 0x54, 0x02, 0x02, 0x00, 0x01, 0xa1,
+		0x0f, 0x08, 0x07, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xb8, 0x0b, 0x00, 0x00,
+		0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x01, 0x02, 0x00, 0x01, 0x01, 0x01, 0x01,
+		0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+		0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+	]),
+	("xcm_transact", &[
+		0x00, 0x06, 0x01, 0x00, 0xca, 0x9a, 0x3b, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x01, 0x02, 0x03,
+	]),
+	("xcm_hrmp_new_channel_open_request", &[
+		0x00, 0x07, 0xa1, 0x0f, 0x01, 0x10, 0x20,
+	]),
+	("xcm_hrmp_channel_accepted", &[
+		0x00, 0x08, 0x41, 0x1f,
+	]),
+	("xcm_hrmp_channel_closing", &[
+		0x00, 0x09, 0xa1, 0x0f, 0xa1, 0x0f, 0x41, 0x1f,
+	]),
+	("xcm_relayed_from", &[
+		0x00, 0x0a, 0x01, 0x02, 0x00, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+		0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+		0x01, 0x01, 0x01, 0x01, 0x01, 0x00, 0x04, 0x0a, 0x01, 0x00, 0x07, 0x00, 0xe4, 0x0b, 0x54, 0x02,
+		0x04, 0x07, 0x01, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x14, 0x00, 0x00, 0x00, 0x00,
+		0x00, 0x00, 0x00, 0x01, 0x04, 0x06, 0x03, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04,
+		0x09,
+	]),
+];
+
+#[test]
+fn corpus_covers_every_sample() {
+	let sample_names = samples().into_iter().map(|(name, _)| name).collect::<Vec<_>>();
+	let golden_names = GOLDEN.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+	assert_eq!(sample_names, golden_names);
+}
+
+#[test]
+fn golden_blobs_decode_to_samples() {
+	for ((name, sample), (_, golden)) in samples().into_iter().zip(GOLDEN.iter()) {
+		assert_eq!(sample.decode_like(golden).as_ref(), Ok(&sample), "{} no longer decodes", name);
+	}
+}
+
+#[test]
+fn samples_reencode_to_golden_blobs() {
+	for ((name, sample), (_, golden)) in samples().into_iter().zip(GOLDEN.iter()) {
+		assert_eq!(&sample.encode()[..], *golden, "{} encodes differently", name);
+		let decoded = sample.decode_like(golden).expect("golden blob decodes; qed");
+		assert_eq!(&decoded.encode()[..], *golden, "{} does not round-trip", name);
+	}
+}
+
+#[test]
+#[ignore]
+fn regenerate_golden_corpus() {
+	extern crate std;
+	std::println!("const GOLDEN: &[(&str, &[u8])] = &[");
+	for (name, sample) in samples() {
+		std::println!("\t(\"{}\", &[", name);
+		for line in sample.encode().chunks(16) {
+			let bytes = line.iter().map(|b| std::format!("0x{:02x}", b)).collect::<Vec<_>>();
+			std::println!("\t\t{},", bytes.join(", "));
+		}
+		std::println!("\t]),");
+	}
+	std::println!("];");
+}
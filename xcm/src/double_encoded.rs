@@ -55,6 +55,9 @@ impl<T> DoubleEncoded<T> {
 	pub fn as_ref(&self) -> Option<&T> {
 		self.decoded.as_ref()
 	}
+	pub(crate) fn encoded(&self) -> &[u8] {
+		&self.encoded
+	}
 }
 
 impl<T: Decode> DoubleEncoded<T> {
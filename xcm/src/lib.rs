@@ -23,7 +23,7 @@
 #![no_std]
 extern crate alloc;
 
-use parity_scale_codec::{Encode, Decode};
+use parity_scale_codec::{Encode, Decode, DecodeLimit};
 use derivative::Derivative;
 
 pub mod v0;
@@ -31,6 +31,36 @@ pub mod v0;
 mod double_encoded;
 pub use double_encoded::DoubleEncoded;
 
+#[cfg(test)]
+mod conformance;
+
+/// A version of the XCM format, as given by the index of the corresponding variant of the `Versioned*` types.
+pub type XcmVersion = u32;
+
+/// Something which is tagged with the XCM version it is expressed in.
+pub trait IdentifyVersion {
+	/// The XCM version of `self`.
+	fn identify_version(&self) -> XcmVersion;
+}
+
+/// Determine the XCM version of an encoded `Versioned*` value without decoding it.
+///
+/// Only the leading enum index is inspected; `None` is returned if `encoded` is empty or begins with an index
+/// which does not correspond to a known version. A `Some` result does not imply that the rest of `encoded` is
+/// well-formed.
+pub fn peek_version(encoded: &[u8]) -> Option<XcmVersion> {
+	match encoded.first() {
+		Some(&0) => Some(0),
+		_ => None,
+	}
+}
+
+/// Maximum nesting level for XCM decoding.
+///
+/// Any XCM blob which arrives from outside of the runtime should be decoded with this limit, since a
+/// maliciously nested message could otherwise exhaust the stack before it is ever executed.
+pub const MAX_XCM_DECODE_DEPTH: u32 = 8;
+
 /// A single XCM message, together with its version code.
 #[derive(Derivative, Encode, Decode)]
 #[derivative(Clone(bound=""), Eq(bound=""), PartialEq(bound=""), Debug(bound=""))]
@@ -40,6 +70,51 @@ pub enum VersionedXcm<Call> {
 	V0(v0::Xcm<Call>),
 }
 
+impl<Call> IdentifyVersion for VersionedXcm<Call> {
+	fn identify_version(&self) -> XcmVersion {
+		match self {
+			VersionedXcm::V0(_) => 0,
+		}
+	}
+}
+
+/// Decode a `VersionedXcm` from `data`, failing if it is nested deeper than `MAX_XCM_DECODE_DEPTH`.
+pub fn decode_xcm_with_limit<Call>(data: &[u8]) -> Result<VersionedXcm<Call>, parity_scale_codec::Error> {
+	VersionedXcm::<Call>::decode_with_depth_limit(MAX_XCM_DECODE_DEPTH, data)
+}
+
+/// The identifier of an XCM message, used in events and logs to correlate it across the systems it passes through.
+pub type XcmHash = [u8; 32];
+
+/// Compute the `XcmHash` of a message from `encoded_versioned`, its encoding as a `VersionedXcm`.
+///
+/// These must be exactly the bytes handed to the transport: the version prefix included, and after any wrapping by
+/// the sender (such as in `RelayedFrom`). The hash is the 256-bit BLAKE2b of them, so that the sender and the
+/// receiver, which only ever has those bytes, arrive at the same value. Never hash the inner `v0::Xcm` instead.
+pub fn xcm_hash(encoded_versioned: &[u8]) -> XcmHash {
+	let mut hash = [0u8; 32];
+	hash.copy_from_slice(blake2_rfc::blake2b::blake2b(32, &[], encoded_versioned).as_bytes());
+	hash
+}
+
+impl<Call> VersionedXcm<Call> {
+	/// The `XcmHash` of this message, as given by `xcm_hash` of its encoding.
+	pub fn hash(&self) -> XcmHash {
+		self.using_encoded(xcm_hash)
+	}
+}
+
+/// The `XcmHash` of `message` as it would be sent, wrapped in `VersionedXcm::V0`.
+///
+/// This is `VersionedXcm::from(message.clone()).hash()` without the clone, for callers which only hold a reference.
+pub fn xcm_hash_v0<Call>(message: &v0::Xcm<Call>) -> XcmHash {
+	let mut encoded = alloc::vec::Vec::with_capacity(1 + message.size_hint());
+	// The index of `VersionedXcm::V0`.
+	encoded.push(0u8);
+	message.encode_to(&mut encoded);
+	xcm_hash(&encoded)
+}
+
 pub mod opaque {
 	pub mod v0 {
 		// Everything from v0
@@ -58,8 +133,76 @@ pub enum VersionedMultiLocation {
 	V0(v0::MultiLocation),
 }
 
+impl IdentifyVersion for VersionedMultiLocation {
+	fn identify_version(&self) -> XcmVersion {
+		match self {
+			VersionedMultiLocation::V0(_) => 0,
+		}
+	}
+}
+
 /// A versioned multi-asset, an identifier for an asset within a consensus system.
 #[derive(Clone, Eq, PartialEq, Encode, Decode, Debug)]
 pub enum VersionedMultiAsset {
 	V0(v0::MultiAsset),
 }
+
+impl IdentifyVersion for VersionedMultiAsset {
+	fn identify_version(&self) -> XcmVersion {
+		match self {
+			VersionedMultiAsset::V0(_) => 0,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+	use v0::{Junction, MultiAsset, MultiLocation, Xcm};
+
+	#[test]
+	fn identify_version_works() {
+		let xcm = VersionedXcm::<()>::V0(Xcm::WithdrawAsset { assets: vec![], effects: vec![] });
+		assert_eq!(xcm.identify_version(), 0);
+		let location = VersionedMultiLocation::V0(MultiLocation::X1(Junction::Parent));
+		assert_eq!(location.identify_version(), 0);
+		let asset = VersionedMultiAsset::V0(MultiAsset::All);
+		assert_eq!(asset.identify_version(), 0);
+	}
+
+	#[test]
+	fn peek_version_matches_identify_version() {
+		let xcm = VersionedXcm::<()>::V0(Xcm::WithdrawAsset { assets: vec![], effects: vec![] });
+		assert_eq!(peek_version(&xcm.encode()), Some(xcm.identify_version()));
+		let location = VersionedMultiLocation::V0(MultiLocation::X1(Junction::Parent));
+		assert_eq!(peek_version(&location.encode()), Some(location.identify_version()));
+		let asset = VersionedMultiAsset::V0(MultiAsset::All);
+		assert_eq!(peek_version(&asset.encode()), Some(asset.identify_version()));
+	}
+
+	#[test]
+	fn xcm_hash_is_blake2_256_of_versioned_encoding() {
+		let xcm = VersionedXcm::<()>::V0(Xcm::QueryResponse { query_id: 0, response: v0::Response::Assets(vec![]) });
+		let encoded = xcm.encode();
+		assert_eq!(encoded, vec![0, 3, 0, 0, 0]);
+		let expected = [
+			0x5f, 0x3c, 0x98, 0x7c, 0x40, 0xb3, 0x03, 0x87, 0x14, 0x9f, 0xf4, 0x16, 0x4d, 0x7e, 0x1d, 0x51,
+			0x44, 0x0c, 0x1a, 0xeb, 0x52, 0x2a, 0x6f, 0x4d, 0x5e, 0x11, 0x67, 0xd4, 0x92, 0x23, 0x65, 0xa3,
+		];
+		assert_eq!(xcm_hash(&encoded), expected);
+		assert_eq!(xcm.hash(), expected);
+
+		// The inner message, without its version prefix, is a different preimage.
+		let VersionedXcm::V0(inner) = xcm;
+		assert_eq!(xcm_hash_v0(&inner), expected);
+		assert_ne!(xcm_hash(&inner.encode()), expected);
+	}
+
+	#[test]
+	fn peek_version_rejects_malformed_input() {
+		assert_eq!(peek_version(&[]), None);
+		assert_eq!(peek_version(&[1]), None);
+		assert_eq!(peek_version(&[0xff, 0, 0]), None);
+	}
+}
@@ -0,0 +1,327 @@
+// Copyright 2021 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A builder for the usual shape of an asset-carrying XCM message: place assets into holding, pay for execution (or
+//! explicitly opt out of doing so) and only then act on the holding.
+//!
+//! The builder's state is tracked in its type, so skipping the fee step is a compile error:
+//!
+//! ```compile_fail
+//! use xcm::v0::{Xcm, MultiAsset::All, MultiLocation::Null};
+//! let _ = Xcm::<()>::builder()
+//!     .withdraw(vec![All])
+//!     .deposit_asset(vec![All], Null)
+//!     .build();
+//! ```
+//!
+//! A `Transact` needs weight to have been bought, so it is not available after `unpaid`:
+//!
+//! ```compile_fail
+//! use xcm::v0::{Xcm, MultiAsset::All, OriginKind};
+//! let _ = Xcm::<()>::builder()
+//!     .withdraw(vec![All])
+//!     .unpaid()
+//!     .transact(OriginKind::Native, 1_000, vec![].into())
+//!     .build();
+//! ```
+//!
+//! [`Xcm::builder_unsafe`] gives an unchecked builder for messages which intentionally deviate from this shape.
+
+use core::marker::PhantomData;
+use alloc::vec::Vec;
+use crate::DoubleEncoded;
+use super::{Xcm, Order, MultiAsset, MultiLocation, OriginKind};
+
+/// Type-level states of an [`XcmBuilder`].
+pub mod state {
+	/// Nothing has been placed into holding yet.
+	pub struct LoadHolding;
+	/// Holding is loaded; execution must now be paid for, or explicitly left unpaid.
+	pub struct PayFees;
+	/// Execution has been bought; effects, including `Transact`, may follow.
+	pub struct Paid;
+	/// The message has been declared as not paying for its execution; effects other than `Transact` may follow.
+	pub struct Unpaid;
+	/// No ordering is enforced.
+	pub struct Unchecked;
+
+	/// States in which orders may be appended and the message built.
+	pub trait Acting {}
+	impl Acting for Paid {}
+	impl Acting for Unpaid {}
+	impl Acting for Unchecked {}
+}
+
+use state::*;
+
+#[derive(Clone, Copy)]
+enum Holding {
+	Withdraw,
+	ReserveDeposit,
+	Teleport,
+}
+
+/// Builds an `Xcm::WithdrawAsset`, `Xcm::ReserveAssetDeposit` or `Xcm::TeleportAsset` message along with its
+/// effects. Created with [`Xcm::builder`] or [`Xcm::builder_unsafe`].
+pub struct XcmBuilder<Call, State> {
+	holding: Holding,
+	assets: Vec<MultiAsset>,
+	effects: Vec<Order<Call>>,
+	_state: PhantomData<State>,
+}
+
+impl<Call> Xcm<Call> {
+	/// Start building a message which must pay for its execution (or explicitly opt out) before acting.
+	pub fn builder() -> XcmBuilder<Call, LoadHolding> {
+		XcmBuilder::new()
+	}
+
+	/// Start building a message without any ordering checks.
+	pub fn builder_unsafe() -> XcmBuilder<Call, Unchecked> {
+		XcmBuilder::new()
+	}
+}
+
+impl<Call, State> XcmBuilder<Call, State> {
+	fn new() -> Self {
+		XcmBuilder { holding: Holding::Withdraw, assets: Vec::new(), effects: Vec::new(), _state: PhantomData }
+	}
+
+	fn into_state<S>(self) -> XcmBuilder<Call, S> {
+		XcmBuilder { holding: self.holding, assets: self.assets, effects: self.effects, _state: PhantomData }
+	}
+
+	fn load(mut self, holding: Holding, assets: Vec<MultiAsset>) -> Self {
+		self.holding = holding;
+		self.assets = assets;
+		self
+	}
+
+	fn push(mut self, order: Order<Call>) -> Self {
+		self.effects.push(order);
+		self
+	}
+}
+
+impl<Call> XcmBuilder<Call, LoadHolding> {
+	/// Withdraw `assets` from the origin's account into holding, i.e. build an `Xcm::WithdrawAsset`.
+	pub fn withdraw(self, assets: Vec<MultiAsset>) -> XcmBuilder<Call, PayFees> {
+		self.load(Holding::Withdraw, assets).into_state()
+	}
+
+	/// Credit `assets`, held in reserve by the origin, into holding, i.e. build an `Xcm::ReserveAssetDeposit`.
+	pub fn reserve_deposit(self, assets: Vec<MultiAsset>) -> XcmBuilder<Call, PayFees> {
+		self.load(Holding::ReserveDeposit, assets).into_state()
+	}
+
+	/// Credit `assets`, teleported away from the origin, into holding, i.e. build an `Xcm::TeleportAsset`.
+	pub fn teleport_received(self, assets: Vec<MultiAsset>) -> XcmBuilder<Call, PayFees> {
+		self.load(Holding::Teleport, assets).into_state()
+	}
+}
+
+impl<Call> XcmBuilder<Call, PayFees> {
+	/// Pay for up to `weight` of execution with up to `fees` from holding.
+	pub fn buy_execution(self, fees: MultiAsset, weight: u64, debt: u64, halt_on_error: bool) -> XcmBuilder<Call, Paid> {
+		self.push(Order::BuyExecution { fees, weight, debt, halt_on_error, xcm: Vec::new() }).into_state()
+	}
+
+	/// Do not pay for execution. The message will only pass a barrier which admits unpaid execution from its origin.
+	pub fn unpaid(self) -> XcmBuilder<Call, Unpaid> {
+		self.into_state()
+	}
+}
+
+impl<Call> XcmBuilder<Call, Paid> {
+	/// Dispatch `call` with the bought weight, as part of the most recent `BuyExecution`.
+	pub fn transact(mut self, origin_type: OriginKind, require_weight_at_most: u64, call: DoubleEncoded<Call>) -> Self {
+		let transact = Xcm::Transact { origin_type, require_weight_at_most, call };
+		match self.effects.iter_mut().rev().find_map(|o| match o {
+			Order::BuyExecution { xcm, .. } => Some(xcm),
+			_ => None,
+		}) {
+			Some(xcm) => xcm.push(transact),
+			None => unreachable!("the `Paid` state is only reachable through `buy_execution`; qed"),
+		}
+		self
+	}
+}
+
+impl<Call> XcmBuilder<Call, Unchecked> {
+	/// Build an `Xcm::WithdrawAsset` of `assets`.
+	pub fn withdraw(self, assets: Vec<MultiAsset>) -> Self {
+		self.load(Holding::Withdraw, assets)
+	}
+
+	/// Build an `Xcm::ReserveAssetDeposit` of `assets`.
+	pub fn reserve_deposit(self, assets: Vec<MultiAsset>) -> Self {
+		self.load(Holding::ReserveDeposit, assets)
+	}
+
+	/// Build an `Xcm::TeleportAsset` of `assets`.
+	pub fn teleport_received(self, assets: Vec<MultiAsset>) -> Self {
+		self.load(Holding::Teleport, assets)
+	}
+
+	/// Append a `BuyExecution` order, wherever it may fall.
+	pub fn buy_execution(self, fees: MultiAsset, weight: u64, debt: u64, halt_on_error: bool) -> Self {
+		self.push(Order::BuyExecution { fees, weight, debt, halt_on_error, xcm: Vec::new() })
+	}
+
+	/// Append an arbitrary order.
+	pub fn order(self, order: Order<Call>) -> Self {
+		self.push(order)
+	}
+}
+
+impl<Call, State: Acting> XcmBuilder<Call, State> {
+	/// Append an `Order::DepositAsset`.
+	pub fn deposit_asset(self, assets: Vec<MultiAsset>, dest: MultiLocation) -> Self {
+		self.push(Order::DepositAsset { assets, dest })
+	}
+
+	/// Append an `Order::DepositReserveAsset`.
+	pub fn deposit_reserve_asset(self, assets: Vec<MultiAsset>, dest: MultiLocation, effects: Vec<Order<()>>) -> Self {
+		self.push(Order::DepositReserveAsset { assets, dest, effects })
+	}
+
+	/// Append an `Order::ExchangeAsset`.
+	pub fn exchange_asset(self, give: Vec<MultiAsset>, receive: Vec<MultiAsset>) -> Self {
+		self.push(Order::ExchangeAsset { give, receive })
+	}
+
+	/// Append an `Order::InitiateReserveWithdraw`.
+	pub fn initiate_reserve_withdraw(
+		self,
+		assets: Vec<MultiAsset>,
+		reserve: MultiLocation,
+		effects: Vec<Order<()>>,
+	) -> Self {
+		self.push(Order::InitiateReserveWithdraw { assets, reserve, effects })
+	}
+
+	/// Append an `Order::InitiateTeleport`.
+	pub fn initiate_teleport(self, assets: Vec<MultiAsset>, dest: MultiLocation, effects: Vec<Order<()>>) -> Self {
+		self.push(Order::InitiateTeleport { assets, dest, effects })
+	}
+
+	/// Append an `Order::QueryHolding`, reporting (up to) `assets` of holding back to `dest`.
+	pub fn query_holding(self, query_id: u64, dest: MultiLocation, assets: Vec<MultiAsset>) -> Self {
+		self.push(Order::QueryHolding { query_id, dest, assets })
+	}
+
+	/// Finish the message.
+	pub fn build(self) -> Xcm<Call> {
+		let XcmBuilder { holding, assets, effects, .. } = self;
+		match holding {
+			Holding::Withdraw => Xcm::WithdrawAsset { assets, effects },
+			Holding::ReserveDeposit => Xcm::ReserveAssetDeposit { assets, effects },
+			Holding::Teleport => Xcm::TeleportAsset { assets, effects },
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use alloc::vec;
+	use crate::v0::{Xcm, Order, OriginKind, MultiAsset::{self, *}, MultiLocation::*, Junction::*};
+
+	fn fees() -> MultiAsset {
+		ConcreteFungible { id: Null, amount: 10 }
+	}
+
+	#[test]
+	fn withdraw_and_deposit_should_match_hand_built_message() {
+		let built = Xcm::<()>::builder()
+			.withdraw(vec![ConcreteFungible { id: Null, amount: 100 }])
+			.buy_execution(fees(), 50, 0, true)
+			.deposit_asset(vec![All], X1(Parachain(2)))
+			.build();
+		let expected = Xcm::<()>::WithdrawAsset {
+			assets: vec![ConcreteFungible { id: Null, amount: 100 }],
+			effects: vec![
+				Order::BuyExecution { fees: fees(), weight: 50, debt: 0, halt_on_error: true, xcm: vec![] },
+				Order::DepositAsset { assets: vec![All], dest: X1(Parachain(2)) },
+			],
+		};
+		assert_eq!(built, expected);
+	}
+
+	#[test]
+	fn query_holding_should_match_hand_built_message() {
+		let built = Xcm::<()>::builder()
+			.reserve_deposit(vec![AllConcreteFungible { id: X1(Parent) }])
+			.unpaid()
+			.deposit_asset(vec![All], X1(Parachain(2)))
+			.query_holding(42, X1(Parent), vec![All])
+			.build();
+		let expected = Xcm::<()>::ReserveAssetDeposit {
+			assets: vec![AllConcreteFungible { id: X1(Parent) }],
+			effects: vec![
+				Order::DepositAsset { assets: vec![All], dest: X1(Parachain(2)) },
+				Order::QueryHolding { query_id: 42, dest: X1(Parent), assets: vec![All] },
+			],
+		};
+		assert_eq!(built, expected);
+	}
+
+	#[test]
+	fn transact_should_use_latest_bought_execution() {
+		let built = Xcm::<()>::builder()
+			.teleport_received(vec![All])
+			.buy_execution(fees(), 1_000, 0, false)
+			.transact(OriginKind::SovereignAccount, 500, vec![1, 2, 3].into())
+			.deposit_asset(vec![All], Null)
+			.build();
+		let expected = Xcm::<()>::TeleportAsset {
+			assets: vec![All],
+			effects: vec![
+				Order::BuyExecution {
+					fees: fees(),
+					weight: 1_000,
+					debt: 0,
+					halt_on_error: false,
+					xcm: vec![Xcm::Transact {
+						origin_type: OriginKind::SovereignAccount,
+						require_weight_at_most: 500,
+						call: vec![1, 2, 3].into(),
+					}],
+				},
+				Order::DepositAsset { assets: vec![All], dest: Null },
+			],
+		};
+		assert_eq!(built, expected);
+	}
+
+	#[test]
+	fn unchecked_builder_should_allow_any_order() {
+		let built = Xcm::<()>::builder_unsafe()
+			.deposit_asset(vec![All], Null)
+			.order(Order::Null)
+			.buy_execution(fees(), 0, 0, false)
+			.teleport_received(vec![All])
+			.build();
+		let expected = Xcm::<()>::TeleportAsset {
+			assets: vec![All],
+			effects: vec![
+				Order::DepositAsset { assets: vec![All], dest: Null },
+				Order::Null,
+				Order::BuyExecution { fees: fees(), weight: 0, debt: 0, halt_on_error: false, xcm: vec![] },
+			],
+		};
+		assert_eq!(built, expected);
+	}
+}
@@ -0,0 +1,138 @@
+// Copyright 2021 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! `Debug` implementations for types carrying byte payloads.
+//!
+//! These have the same shape as the derived implementations, but print bytes as `0x`-prefixed hex (followed by the
+//! text, when it is all printable ASCII) instead of as a list of decimal numbers.
+
+use core::fmt::{self, Debug, Formatter};
+use super::{Junction, AssetInstance};
+use super::display::{Hex, HEX_TRUNCATE_ABOVE};
+
+/// Wrapper giving a byte slice a `Debug` representation such as `0x424e43 "BNC"`: the shared `Hex` rendering,
+/// followed by the text when the payload is short enough to be shown whole and is all printable ASCII.
+struct Bytes<'a>(&'a [u8]);
+
+impl<'a> Debug for Bytes<'a> {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "{}", Hex(self.0))?;
+		if self.0.len() <= HEX_TRUNCATE_ABOVE && !self.0.is_empty() && self.0.iter().all(|b| (b' '..=b'~').contains(b)) {
+			// Every byte is printable ASCII, so this is valid UTF-8.
+			write!(f, " {:?}", core::str::from_utf8(self.0).map_err(|_| fmt::Error)?)
+		} else {
+			Ok(())
+		}
+	}
+}
+
+impl Debug for Junction {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Junction::Parent => f.write_str("Parent"),
+			Junction::Parachain(id) => f.debug_tuple("Parachain").field(id).finish(),
+			Junction::AccountId32 { network, id } => f.debug_struct("AccountId32")
+				.field("network", network)
+				.field("id", &Bytes(id))
+				.finish(),
+			Junction::AccountIndex64 { network, index } => f.debug_struct("AccountIndex64")
+				.field("network", network)
+				.field("index", index)
+				.finish(),
+			Junction::AccountKey20 { network, key } => f.debug_struct("AccountKey20")
+				.field("network", network)
+				.field("key", &Bytes(key))
+				.finish(),
+			Junction::PalletInstance(index) => f.debug_tuple("PalletInstance").field(index).finish(),
+			Junction::GeneralIndex { id } => f.debug_struct("GeneralIndex").field("id", id).finish(),
+			Junction::GeneralKey(key) => f.debug_tuple("GeneralKey").field(&Bytes(key)).finish(),
+			Junction::OnlyChild => f.write_str("OnlyChild"),
+			Junction::Plurality { id, part } => f.debug_struct("Plurality")
+				.field("id", id)
+				.field("part", part)
+				.finish(),
+		}
+	}
+}
+
+impl Debug for AssetInstance {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			AssetInstance::Undefined => f.write_str("Undefined"),
+			AssetInstance::Index { id } => f.debug_struct("Index").field("id", id).finish(),
+			AssetInstance::Array4(a) => f.debug_tuple("Array4").field(&Bytes(a)).finish(),
+			AssetInstance::Array8(a) => f.debug_tuple("Array8").field(&Bytes(a)).finish(),
+			AssetInstance::Array16(a) => f.debug_tuple("Array16").field(&Bytes(a)).finish(),
+			AssetInstance::Array32(a) => f.debug_tuple("Array32").field(&Bytes(a)).finish(),
+			AssetInstance::Blob(b) => f.debug_tuple("Blob").field(&Bytes(b)).finish(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::{format, vec};
+	use crate::v0::{NetworkId, BodyId, BodyPart};
+
+	#[test]
+	fn general_key_shows_hex_and_text() {
+		assert_eq!(format!("{:?}", Junction::GeneralKey(b"BNC".to_vec())), r#"GeneralKey(0x424e43 "BNC")"#);
+		assert_eq!(format!("{:?}", Junction::GeneralKey(vec![0, 1, 255])), "GeneralKey(0x0001ff)");
+		assert_eq!(format!("{:?}", Junction::GeneralKey(vec![])), "GeneralKey(0x)");
+	}
+
+	#[test]
+	fn account_junctions_show_hex() {
+		let mut id = [0u8; 32];
+		id[0] = 0xd4;
+		id[31] = 0x7d;
+		assert_eq!(
+			format!("{:?}", Junction::AccountId32 { network: NetworkId::Any, id }),
+			format!("AccountId32 {{ network: Any, id: 0xd4{}7d }}", "00".repeat(30)),
+		);
+		assert_eq!(
+			format!("{:?}", Junction::AccountKey20 { network: NetworkId::Kusama, key: [0xab; 20] }),
+			"AccountKey20 { network: Kusama, key: 0xabababababababababababababababababababab }",
+		);
+	}
+
+	#[test]
+	fn long_payloads_are_truncated() {
+		let blob = (0u8..40).collect::<alloc::vec::Vec<_>>();
+		assert_eq!(
+			format!("{:?}", AssetInstance::Blob(blob)),
+			"Blob(0x00010203…24252627 (40 bytes))",
+		);
+		assert_eq!(
+			format!("{:?}", AssetInstance::Blob(b"ordinal #1".to_vec())),
+			r#"Blob(0x6f7264696e616c202331 "ordinal #1")"#,
+		);
+	}
+
+	#[test]
+	fn other_variants_match_derived_output() {
+		assert_eq!(format!("{:?}", Junction::Parent), "Parent");
+		assert_eq!(format!("{:?}", Junction::Parachain(1000)), "Parachain(1000)");
+		assert_eq!(format!("{:?}", Junction::GeneralIndex { id: 42 }), "GeneralIndex { id: 42 }");
+		assert_eq!(
+			format!("{:?}", Junction::Plurality { id: BodyId::Executive, part: BodyPart::Voice }),
+			"Plurality { id: Executive, part: Voice }",
+		);
+		assert_eq!(format!("{:?}", AssetInstance::Index { id: 7 }), "Index { id: 7 }");
+		assert_eq!(format!("{:#?}", Junction::PalletInstance(5)), "PalletInstance(\n    5,\n)");
+	}
+}
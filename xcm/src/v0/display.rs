@@ -0,0 +1,411 @@
+// Copyright 2021 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Human-readable `Display` formatting for XCM messages and their components.
+//!
+//! Messages are printed one instruction per line, with the orders and nested messages of an instruction indented
+//! beneath it. Locations use a path form such as `../Parachain(2000)/PalletInstance(50)`, amounts are grouped with
+//! underscores and byte payloads are shown as hex, truncated in the middle when long.
+//!
+//! Nothing here allocates; everything is written straight into the formatter.
+
+use core::fmt::{self, Display, Formatter};
+use super::{Xcm, Order, Response, Junction, NetworkId, MultiLocation, MultiAsset, AssetInstance};
+
+/// Byte payloads longer than this (i.e. longer than an account ID) are shown as their first and last `HEX_EDGE`
+/// bytes, followed by their total length.
+pub(crate) const HEX_TRUNCATE_ABOVE: usize = 32;
+const HEX_EDGE: usize = 4;
+
+/// Wrapper displaying a byte slice as `0x`-prefixed hex, truncated in the middle when long. This is the one rendering
+/// of bytes used by both the `Display` and the `Debug` implementations of this module's types.
+pub(crate) struct Hex<'a>(pub &'a [u8]);
+
+impl<'a> Display for Hex<'a> {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_str("0x")?;
+		if self.0.len() > HEX_TRUNCATE_ABOVE {
+			for b in &self.0[..HEX_EDGE] { write!(f, "{:02x}", b)?; }
+			f.write_str("…")?;
+			for b in &self.0[self.0.len() - HEX_EDGE..] { write!(f, "{:02x}", b)?; }
+			write!(f, " ({} bytes)", self.0.len())
+		} else {
+			for b in self.0 { write!(f, "{:02x}", b)?; }
+			Ok(())
+		}
+	}
+}
+
+/// Wrapper displaying an amount with its digits grouped in threes by underscores, e.g. `10_000_000`.
+struct Amount(u128);
+
+impl Display for Amount {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		// `u128::MAX` has 39 digits.
+		let mut digits = [0u8; 39];
+		let mut n = self.0;
+		let mut len = 0;
+		loop {
+			digits[len] = b'0' + (n % 10) as u8;
+			len += 1;
+			n /= 10;
+			if n == 0 { break }
+		}
+		for i in (0..len).rev() {
+			write!(f, "{}", digits[i] as char)?;
+			if i > 0 && i % 3 == 0 {
+				f.write_str("_")?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl Display for NetworkId {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			// The same spelling as `FromStr` accepts (and `as_str` gives), so that networks can be parsed back.
+			NetworkId::Any => f.write_str("any"),
+			NetworkId::Named(name) => write!(f, "named:{}", Hex(name)),
+			NetworkId::Polkadot => f.write_str("polkadot"),
+			NetworkId::Kusama => f.write_str("kusama"),
+		}
+	}
+}
+
+/// Write the `@network` suffix of an account junction, omitted for `NetworkId::Any`.
+fn fmt_network(network: &NetworkId, f: &mut Formatter) -> fmt::Result {
+	match network {
+		NetworkId::Any => Ok(()),
+		network => write!(f, "@{}", network),
+	}
+}
+
+impl Display for Junction {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Junction::Parent => f.write_str(".."),
+			Junction::Parachain(id) => write!(f, "Parachain({})", id),
+			Junction::AccountId32 { network, id } => {
+				write!(f, "AccountId32({})", Hex(id))?;
+				fmt_network(network, f)
+			}
+			Junction::AccountIndex64 { network, index } => {
+				write!(f, "AccountIndex64({})", index)?;
+				fmt_network(network, f)
+			}
+			Junction::AccountKey20 { network, key } => {
+				write!(f, "AccountKey20({})", Hex(key))?;
+				fmt_network(network, f)
+			}
+			Junction::PalletInstance(index) => write!(f, "PalletInstance({})", index),
+			Junction::GeneralIndex { id } => write!(f, "GeneralIndex({})", id),
+			Junction::GeneralKey(key) => write!(f, "GeneralKey({})", Hex(key)),
+			Junction::OnlyChild => f.write_str("OnlyChild"),
+			Junction::Plurality { id, part } => write!(f, "Plurality({:?}, {:?})", id, part),
+		}
+	}
+}
+
+impl Display for MultiLocation {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		if let MultiLocation::Null = self {
+			return f.write_str(".")
+		}
+		for (i, junction) in self.iter().enumerate() {
+			if i > 0 {
+				f.write_str("/")?;
+			}
+			write!(f, "{}", junction)?;
+		}
+		Ok(())
+	}
+}
+
+impl Display for AssetInstance {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			AssetInstance::Undefined => f.write_str("Undefined"),
+			AssetInstance::Index { id } => write!(f, "#{}", id),
+			AssetInstance::Array4(bytes) => write!(f, "{}", Hex(bytes)),
+			AssetInstance::Array8(bytes) => write!(f, "{}", Hex(bytes)),
+			AssetInstance::Array16(bytes) => write!(f, "{}", Hex(bytes)),
+			AssetInstance::Array32(bytes) => write!(f, "{}", Hex(bytes)),
+			AssetInstance::Blob(bytes) => write!(f, "{}", Hex(bytes)),
+		}
+	}
+}
+
+impl Display for MultiAsset {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			MultiAsset::None => f.write_str("None"),
+			MultiAsset::All => f.write_str("All"),
+			MultiAsset::AllFungible => f.write_str("AllFungible"),
+			MultiAsset::AllNonFungible => f.write_str("AllNonFungible"),
+			MultiAsset::AllAbstractFungible { id } => write!(f, "AllFungible({})", Hex(id)),
+			MultiAsset::AllAbstractNonFungible { class } => write!(f, "AllNonFungible({})", Hex(class)),
+			MultiAsset::AllConcreteFungible { id } => write!(f, "AllFungible({})", id),
+			MultiAsset::AllConcreteNonFungible { class } => write!(f, "AllNonFungible({})", class),
+			MultiAsset::AbstractFungible { id, amount } => write!(f, "{} of {}", Amount(*amount), Hex(id)),
+			MultiAsset::AbstractNonFungible { class, instance } => write!(f, "{} of {}", instance, Hex(class)),
+			MultiAsset::ConcreteFungible { id, amount } => write!(f, "{} of {}", Amount(*amount), id),
+			MultiAsset::ConcreteNonFungible { class, instance } => write!(f, "{} of {}", instance, class),
+		}
+	}
+}
+
+/// Wrapper displaying a list of assets as `[a, b, ...]`.
+struct Assets<'a>(&'a [MultiAsset]);
+
+impl<'a> Display for Assets<'a> {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_str("[")?;
+		for (i, asset) in self.0.iter().enumerate() {
+			if i > 0 {
+				f.write_str(", ")?;
+			}
+			write!(f, "{}", asset)?;
+		}
+		f.write_str("]")
+	}
+}
+
+/// Start a new line at the given nesting `depth`. The very first line of the output is not preceded by a newline.
+fn new_line(f: &mut Formatter, depth: usize) -> fmt::Result {
+	if depth > 0 {
+		f.write_str("\n")?;
+		for _ in 0..depth {
+			f.write_str("  ")?;
+		}
+	}
+	Ok(())
+}
+
+fn fmt_orders<Call>(orders: &[Order<Call>], f: &mut Formatter, depth: usize) -> fmt::Result {
+	for order in orders {
+		fmt_order(order, f, depth)?;
+	}
+	Ok(())
+}
+
+fn fmt_order<Call>(order: &Order<Call>, f: &mut Formatter, depth: usize) -> fmt::Result {
+	new_line(f, depth)?;
+	match order {
+		Order::Null => f.write_str("Null"),
+		Order::DepositAsset { assets, dest } =>
+			write!(f, "DepositAsset {} -> {}", Assets(assets), dest),
+		Order::DepositReserveAsset { assets, dest, effects } => {
+			write!(f, "DepositReserveAsset {} -> {}", Assets(assets), dest)?;
+			fmt_orders(effects, f, depth + 1)
+		}
+		Order::ExchangeAsset { give, receive } =>
+			write!(f, "ExchangeAsset {} for {}", Assets(give), Assets(receive)),
+		Order::InitiateReserveWithdraw { assets, reserve, effects } => {
+			write!(f, "InitiateReserveWithdraw {} from {}", Assets(assets), reserve)?;
+			fmt_orders(effects, f, depth + 1)
+		}
+		Order::InitiateTeleport { assets, dest, effects } => {
+			write!(f, "InitiateTeleport {} -> {}", Assets(assets), dest)?;
+			fmt_orders(effects, f, depth + 1)
+		}
+		Order::QueryHolding { query_id, dest, assets } =>
+			write!(f, "QueryHolding #{} {} -> {}", query_id, Assets(assets), dest),
+		Order::BuyExecution { fees, weight, debt, halt_on_error, xcm } => {
+			write!(
+				f,
+				"BuyExecution fees: {}, weight: {}, debt: {}, halt_on_error: {}",
+				fees, Amount(*weight as u128), Amount(*debt as u128), halt_on_error,
+			)?;
+			for message in xcm {
+				fmt_xcm(message, f, depth + 1)?;
+			}
+			Ok(())
+		}
+	}
+}
+
+fn fmt_xcm<Call>(message: &Xcm<Call>, f: &mut Formatter, depth: usize) -> fmt::Result {
+	new_line(f, depth)?;
+	match message {
+		Xcm::WithdrawAsset { assets, effects } => {
+			write!(f, "WithdrawAsset {}", Assets(assets))?;
+			fmt_orders(effects, f, depth + 1)
+		}
+		Xcm::ReserveAssetDeposit { assets, effects } => {
+			write!(f, "ReserveAssetDeposit {}", Assets(assets))?;
+			fmt_orders(effects, f, depth + 1)
+		}
+		Xcm::TeleportAsset { assets, effects } => {
+			write!(f, "TeleportAsset {}", Assets(assets))?;
+			fmt_orders(effects, f, depth + 1)
+		}
+		Xcm::QueryResponse { query_id, response: Response::Assets(assets) } =>
+			write!(f, "QueryResponse #{} {}", query_id, Assets(assets)),
+		Xcm::TransferAsset { assets, dest } =>
+			write!(f, "TransferAsset {} -> {}", Assets(assets), dest),
+		Xcm::TransferReserveAsset { assets, dest, effects } => {
+			write!(f, "TransferReserveAsset {} -> {}", Assets(assets), dest)?;
+			fmt_orders(effects, f, depth + 1)
+		}
+		Xcm::Transact { origin_type, require_weight_at_most, call } => write!(
+			f,
+			"Transact as {:?}, require_weight_at_most: {}, call: {}",
+			origin_type, Amount(*require_weight_at_most as u128), Hex(call.encoded()),
+		),
+		Xcm::HrmpNewChannelOpenRequest { sender, max_message_size, max_capacity } => write!(
+			f,
+			"HrmpNewChannelOpenRequest sender: {}, max_message_size: {}, max_capacity: {}",
+			sender, max_message_size, max_capacity,
+		),
+		Xcm::HrmpChannelAccepted { recipient } =>
+			write!(f, "HrmpChannelAccepted recipient: {}", recipient),
+		Xcm::HrmpChannelClosing { initiator, sender, recipient } => write!(
+			f,
+			"HrmpChannelClosing initiator: {}, sender: {}, recipient: {}",
+			initiator, sender, recipient,
+		),
+		Xcm::RelayedFrom { who, message } => {
+			write!(f, "RelayedFrom {}", who)?;
+			fmt_xcm(message, f, depth + 1)
+		}
+	}
+}
+
+impl<Call> Display for Order<Call> {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		fmt_order(self, f, 0)
+	}
+}
+
+impl<Call> Display for Xcm<Call> {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		fmt_xcm(self, f, 0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::{boxed::Box, format, vec};
+	use crate::v0::OriginKind;
+
+	#[test]
+	fn amounts_are_grouped() {
+		assert_eq!(format!("{}", Amount(0)), "0");
+		assert_eq!(format!("{}", Amount(999)), "999");
+		assert_eq!(format!("{}", Amount(1_000)), "1_000");
+		assert_eq!(format!("{}", Amount(10_000_000_000)), "10_000_000_000");
+		assert_eq!(format!("{}", Amount(u128::MAX)), "340_282_366_920_938_463_463_374_607_431_768_211_455");
+	}
+
+	#[test]
+	fn long_bytes_are_truncated() {
+		assert_eq!(format!("{}", Hex(&[])), "0x");
+		assert_eq!(format!("{}", Hex(&[0xab; 16])), "0xabababababababababababababababab");
+		assert_eq!(format!("{}", Hex(&[0xab; 32])), format!("0x{}", "ab".repeat(32)));
+		assert_eq!(format!("{}", Hex(&(0u8..33).collect::<vec::Vec<_>>())), "0x00010203…1d1e1f20 (33 bytes)");
+	}
+
+	#[test]
+	fn locations_use_path_form() {
+		use Junction::*;
+		assert_eq!(format!("{}", MultiLocation::Null), ".");
+		assert_eq!(format!("{}", MultiLocation::X2(Parent, Parachain(2000))), "../Parachain(2000)");
+		assert_eq!(
+			format!("{}", MultiLocation::X3(Parachain(1000), PalletInstance(50), GeneralIndex { id: 7 })),
+			"Parachain(1000)/PalletInstance(50)/GeneralIndex(7)",
+		);
+		assert_eq!(
+			format!("{}", MultiLocation::X1(AccountIndex64 { network: NetworkId::Kusama, index: 42 })),
+			"AccountIndex64(42)@kusama",
+		);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn networks_display_as_they_parse() {
+		let networks = vec![
+			NetworkId::Any,
+			NetworkId::Polkadot,
+			NetworkId::Kusama,
+			NetworkId::Named(b"westend".to_vec()),
+			NetworkId::Named(vec![0xff; super::super::MAX_PARSED_NAME_LEN]),
+		];
+		for network in networks {
+			let shown = format!("{}", network);
+			assert_eq!(shown.parse::<NetworkId>(), Ok(network), "{} does not parse back", shown);
+		}
+		assert_eq!(format!("{}", NetworkId::Kusama), "kusama");
+		assert_eq!(format!("{}", NetworkId::Named(b"eth".to_vec())), "named:0x657468");
+	}
+
+	#[test]
+	fn reserve_transfer_program() {
+		use Junction::*;
+		let message = Xcm::<()>::TransferReserveAsset {
+			assets: vec![MultiAsset::ConcreteFungible { id: MultiLocation::X1(Parent), amount: 10_000_000_000 }],
+			dest: MultiLocation::X2(Parent, Parachain(2000)),
+			effects: vec![
+				Order::BuyExecution { fees: MultiAsset::All, weight: 0, debt: 3_000, halt_on_error: false, xcm: vec![] },
+				Order::DepositAsset {
+					assets: vec![MultiAsset::All],
+					dest: MultiLocation::X1(AccountId32 { network: NetworkId::Any, id: [1; 32] }),
+				},
+			],
+		};
+		assert_eq!(format!("{}", message), "\
+TransferReserveAsset [10_000_000_000 of ..] -> ../Parachain(2000)
+  BuyExecution fees: All, weight: 0, debt: 3_000, halt_on_error: false
+  DepositAsset [All] -> AccountId32(0x0101010101010101010101010101010101010101010101010101010101010101)");
+	}
+
+	#[test]
+	fn nested_program() {
+		use Junction::*;
+		let message = Xcm::<()>::RelayedFrom {
+			who: MultiLocation::X1(AccountKey20 { network: NetworkId::Polkadot, key: [2; 20] }),
+			message: Box::new(Xcm::WithdrawAsset {
+				assets: vec![MultiAsset::AbstractFungible { id: b"DOT".to_vec(), amount: 1_500 }],
+				effects: vec![
+					Order::BuyExecution { fees: MultiAsset::All, weight: 1_000_000, debt: 0, halt_on_error: true, xcm: vec![
+						Xcm::Transact {
+							origin_type: OriginKind::SovereignAccount,
+							require_weight_at_most: 1_000_000,
+							call: vec![0, 1, 2].into(),
+						},
+						Xcm::TransferAsset { assets: vec![MultiAsset::All], dest: MultiLocation::X1(Parent) },
+					] },
+					Order::InitiateTeleport {
+						assets: vec![MultiAsset::AllConcreteFungible { id: MultiLocation::Null }],
+						dest: MultiLocation::X1(Parent),
+						effects: vec![
+							Order::QueryHolding { query_id: 3, dest: MultiLocation::X1(Parent), assets: vec![MultiAsset::All] },
+						],
+					},
+				],
+			}),
+		};
+		assert_eq!(format!("{}", message), "\
+RelayedFrom AccountKey20(0x0202020202020202020202020202020202020202)@polkadot
+  WithdrawAsset [1_500 of 0x444f54]
+    BuyExecution fees: All, weight: 1_000_000, debt: 0, halt_on_error: true
+      Transact as SovereignAccount, require_weight_at_most: 1_000_000, call: 0x000102
+      TransferAsset [All] -> ..
+    InitiateTeleport [AllFungible(.)] -> ..
+      QueryHolding #3 [All] -> ..");
+	}
+}
@@ -54,6 +54,131 @@ pub enum BodyId {
 	Judicial,
 }
 
+impl NetworkId {
+	/// The canonical lowercase name of the network, or `None` for `Named`, which has no fixed name.
+	pub fn as_str(&self) -> Option<&'static str> {
+		match self {
+			NetworkId::Any => Some("any"),
+			NetworkId::Named(_) => None,
+			NetworkId::Polkadot => Some("polkadot"),
+			NetworkId::Kusama => Some("kusama"),
+		}
+	}
+}
+
+impl BodyId {
+	/// The canonical lowercase name of the body, or `None` for `Named` and `Index`, which have no fixed name.
+	pub fn as_str(&self) -> Option<&'static str> {
+		match self {
+			BodyId::Unit => Some("unit"),
+			BodyId::Named(_) | BodyId::Index { .. } => None,
+			BodyId::Executive => Some("executive"),
+			BodyId::Technical => Some("technical"),
+			BodyId::Legislative => Some("legislative"),
+			BodyId::Judicial => Some("judicial"),
+		}
+	}
+}
+
+/// The longest name, in bytes, accepted when parsing a `Named` network or body from a string.
+pub const MAX_PARSED_NAME_LEN: usize = 32;
+
+/// Error parsing a `NetworkId` or `BodyId` from a string.
+#[cfg(feature = "std")]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum IdParseError {
+	/// The string is not a known identifier nor of the form `named:…` (or, for bodies, `index:…`).
+	Unknown,
+	/// A `named:` name is empty.
+	EmptyName,
+	/// A `named:` name is longer than `MAX_PARSED_NAME_LEN` bytes; the length is given.
+	NameTooLong(usize),
+	/// A `named:0x…` name is not valid hex.
+	InvalidHex,
+	/// An `index:` value is not a valid `u32`.
+	InvalidIndex,
+}
+
+/// Parse the part of a `named:` identifier after the prefix, either `0x`-prefixed hex or the UTF-8 bytes as given.
+#[cfg(feature = "std")]
+fn parse_name(s: &str) -> Result<Vec<u8>, IdParseError> {
+	let name = match s.strip_prefix("0x") {
+		Some(hex) => {
+			let hex = hex.as_bytes();
+			if hex.len() % 2 != 0 {
+				return Err(IdParseError::InvalidHex);
+			}
+			let nibble = |c: u8| (c as char).to_digit(16).ok_or(IdParseError::InvalidHex);
+			hex.chunks(2)
+				.map(|pair| Ok((nibble(pair[0])? * 16 + nibble(pair[1])?) as u8))
+				.collect::<Result<Vec<u8>, _>>()?
+		}
+		None => s.as_bytes().to_vec(),
+	};
+	match name.len() {
+		0 => Err(IdParseError::EmptyName),
+		len if len > MAX_PARSED_NAME_LEN => Err(IdParseError::NameTooLong(len)),
+		_ => Ok(name),
+	}
+}
+
+/// Parses `any`, `polkadot`, `kusama`, `named:0x…` (hex) or `named:…` (UTF-8).
+#[cfg(feature = "std")]
+impl core::str::FromStr for NetworkId {
+	type Err = IdParseError;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"any" => Ok(NetworkId::Any),
+			"polkadot" => Ok(NetworkId::Polkadot),
+			"kusama" => Ok(NetworkId::Kusama),
+			s => match s.strip_prefix("named:") {
+				Some(name) => Ok(NetworkId::Named(parse_name(name)?)),
+				None => Err(IdParseError::Unknown),
+			},
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl core::convert::TryFrom<&str> for NetworkId {
+	type Error = IdParseError;
+	fn try_from(s: &str) -> Result<Self, Self::Error> {
+		s.parse()
+	}
+}
+
+/// Parses `unit`, `executive`, `technical`, `legislative`, `judicial`, `index:<u32>`, `named:0x…` (hex) or
+/// `named:…` (UTF-8).
+#[cfg(feature = "std")]
+impl core::str::FromStr for BodyId {
+	type Err = IdParseError;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"unit" => Ok(BodyId::Unit),
+			"executive" => Ok(BodyId::Executive),
+			"technical" => Ok(BodyId::Technical),
+			"legislative" => Ok(BodyId::Legislative),
+			"judicial" => Ok(BodyId::Judicial),
+			s => if let Some(name) = s.strip_prefix("named:") {
+				Ok(BodyId::Named(parse_name(name)?))
+			} else if let Some(index) = s.strip_prefix("index:") {
+				let id = index.parse().map_err(|_| IdParseError::InvalidIndex)?;
+				Ok(BodyId::Index { id })
+			} else {
+				Err(IdParseError::Unknown)
+			},
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl core::convert::TryFrom<&str> for BodyId {
+	type Error = IdParseError;
+	fn try_from(s: &str) -> Result<Self, Self::Error> {
+		s.parse()
+	}
+}
+
 /// A part of a pluralistic body.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, Debug)]
 pub enum BodyPart {
@@ -73,9 +198,10 @@ impl BodyPart {
 	/// Returns `true` if the part represents a strict majority (> 50%) of the body in question.
 	pub fn is_majority(&self) -> bool {
 		match self {
-			BodyPart::Fraction { nom, denom } if *nom * 2 > *denom => true,
-			BodyPart::AtLeastProportion { nom, denom } if *nom * 2 > *denom => true,
-			BodyPart::MoreThanProportion { nom, denom } if *nom * 2 >= *denom => true,
+			// Widened so that doubling `nom` cannot overflow.
+			BodyPart::Fraction { nom, denom } if *nom as u64 * 2 > *denom as u64 => true,
+			BodyPart::AtLeastProportion { nom, denom } if *nom as u64 * 2 > *denom as u64 => true,
+			BodyPart::MoreThanProportion { nom, denom } if *nom as u64 * 2 >= *denom as u64 => true,
 			_ => false,
 		}
 	}
@@ -84,7 +210,9 @@ impl BodyPart {
 /// A single item in a path to describe the relative location of a consensus system.
 ///
 /// Each item assumes a pre-existing location as its context and is defined in terms of it.
-#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, Debug)]
+///
+/// `Debug` is implemented by hand (in the `debug` module) so that byte payloads are shown as hex.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode)]
 pub enum Junction {
 	/// The consensus system of which the context is a member and state-wise super-set.
 	///
@@ -157,3 +285,84 @@ impl Junction {
 		}
 	}
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+	use super::*;
+	use alloc::{format, vec, string::String};
+	use core::convert::TryFrom;
+
+	#[test]
+	fn network_ids_round_trip() {
+		for network in [NetworkId::Any, NetworkId::Polkadot, NetworkId::Kusama].iter() {
+			let name = network.as_str().unwrap();
+			assert_eq!(name.parse::<NetworkId>().as_ref(), Ok(network));
+			assert_eq!(NetworkId::try_from(name).as_ref(), Ok(network));
+		}
+		assert_eq!("named:westend".parse(), Ok(NetworkId::Named(b"westend".to_vec())));
+		assert_eq!("named:0x00ff".parse(), Ok(NetworkId::Named(vec![0, 255])));
+		assert_eq!(NetworkId::Named(vec![1]).as_str(), None);
+	}
+
+	#[test]
+	fn body_ids_round_trip() {
+		let named = [BodyId::Unit, BodyId::Executive, BodyId::Technical, BodyId::Legislative, BodyId::Judicial];
+		for body in named.iter() {
+			let name = body.as_str().unwrap();
+			assert_eq!(name.parse::<BodyId>().as_ref(), Ok(body));
+			assert_eq!(BodyId::try_from(name).as_ref(), Ok(body));
+		}
+		assert_eq!("index:42".parse(), Ok(BodyId::Index { id: 42 }));
+		assert_eq!("named:fellows".parse(), Ok(BodyId::Named(b"fellows".to_vec())));
+		assert_eq!("named:0xABcd".parse(), Ok(BodyId::Named(vec![0xab, 0xcd])));
+		assert_eq!(BodyId::Index { id: 42 }.as_str(), None);
+		assert_eq!(BodyId::Named(vec![1]).as_str(), None);
+	}
+
+	#[test]
+	fn bad_identifiers_are_rejected() {
+		assert_eq!("Kusama".parse::<NetworkId>(), Err(IdParseError::Unknown));
+		assert_eq!("".parse::<NetworkId>(), Err(IdParseError::Unknown));
+		assert_eq!("named:".parse::<NetworkId>(), Err(IdParseError::EmptyName));
+		assert_eq!("named:0x".parse::<NetworkId>(), Err(IdParseError::EmptyName));
+		assert_eq!("named:0x0".parse::<NetworkId>(), Err(IdParseError::InvalidHex));
+		assert_eq!("named:0xzz".parse::<NetworkId>(), Err(IdParseError::InvalidHex));
+		assert_eq!("named:0x€a".parse::<NetworkId>(), Err(IdParseError::InvalidHex));
+		let long = format!("named:{}", "a".repeat(MAX_PARSED_NAME_LEN + 1));
+		assert_eq!(long.parse::<NetworkId>(), Err(IdParseError::NameTooLong(MAX_PARSED_NAME_LEN + 1)));
+		assert_eq!("council".parse::<BodyId>(), Err(IdParseError::Unknown));
+		assert_eq!("index:".parse::<BodyId>(), Err(IdParseError::InvalidIndex));
+		assert_eq!("index:4294967296".parse::<BodyId>(), Err(IdParseError::InvalidIndex));
+		assert_eq!("index:-1".parse::<BodyId>(), Err(IdParseError::InvalidIndex));
+	}
+
+	#[test]
+	fn is_majority_does_not_overflow() {
+		let max = u32::MAX;
+		assert!(BodyPart::Fraction { nom: max / 2 + 1, denom: max }.is_majority());
+		assert!(!BodyPart::Fraction { nom: max / 2, denom: max }.is_majority());
+		assert!(BodyPart::AtLeastProportion { nom: max, denom: max }.is_majority());
+		assert!(BodyPart::MoreThanProportion { nom: max / 2 + 1, denom: max }.is_majority());
+	}
+
+	#[test]
+	fn arbitrary_strings_do_not_panic() {
+		const PIECES: [&str; 12] = ["named:", "index:", "0x", "0", "f", "g", "€", "kusama", "unit", ":", "9", ""];
+		// A xorshift generator, so the strings are arbitrary but the same on every run.
+		let mut seed = 0x2545_f491_4f6c_dd1du64;
+		let mut next = move || {
+			seed ^= seed << 13;
+			seed ^= seed >> 7;
+			seed ^= seed << 17;
+			seed
+		};
+		for _ in 0..10_000 {
+			let mut s = String::new();
+			for _ in 0..next() % 5 {
+				s.push_str(PIECES[(next() % PIECES.len() as u64) as usize]);
+			}
+			let _ = s.parse::<NetworkId>();
+			let _ = s.parse::<BodyId>();
+		}
+	}
+}
@@ -27,10 +27,16 @@ mod multi_asset;
 mod multi_location;
 mod order;
 mod traits;
-pub use junction::{Junction, NetworkId, BodyId, BodyPart};
+mod display;
+mod debug;
+mod builder;
+pub use junction::{Junction, NetworkId, BodyId, BodyPart, MAX_PARSED_NAME_LEN};
+#[cfg(feature = "std")]
+pub use junction::IdParseError;
 pub use multi_asset::{MultiAsset, AssetInstance};
 pub use multi_location::MultiLocation;
 pub use order::Order;
+pub use builder::{XcmBuilder, state as builder_state};
 pub use traits::{Error, Result, SendXcm, ExecuteXcm, Outcome};
 
 /// A prelude for importing all types typically used when interacting with XCM messages.
@@ -23,7 +23,9 @@ use parity_scale_codec::{self, Encode, Decode};
 use super::{MultiLocation, VersionedMultiAsset};
 
 /// A general identifier for an instance of a non-fungible asset class.
-#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, Debug)]
+///
+/// The array and blob payloads are shown as hex by `Debug`; see the `debug` module.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode)]
 pub enum AssetInstance {
 	/// Undefined - used if the NFA class has only one instance.
 	Undefined,
@@ -79,6 +79,11 @@ pub enum Error {
 	TooExpensive,
 	/// The given asset is not handled.
 	AssetNotFound,
+	/// No deal is possible under the given constraints.
+	///
+	/// Used by:
+	/// - `Order::ExchangeAsset`
+	NoDeal,
 }
 
 impl From<()> for Error {
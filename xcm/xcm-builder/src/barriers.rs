@@ -18,7 +18,7 @@ use sp_std::{result::Result, marker::PhantomData};
 use xcm::v0::{Xcm, Order, MultiLocation, Junction};
 use frame_support::{ensure, traits::Contains, weights::Weight};
 use xcm_executor::traits::{OnResponse, ShouldExecute};
-use polkadot_parachain::primitives::IsSystem;
+use polkadot_parachain::primitives::{IsSystem, Id as ParaId};
 
 pub struct TakeWeightCredit;
 impl ShouldExecute for TakeWeightCredit {
@@ -73,12 +73,22 @@ impl<T: Contains<MultiLocation>> ShouldExecute for AllowUnpaidExecutionFrom<T> {
 	}
 }
 
-pub struct IsChildSystemParachain<ParaId>(PhantomData<ParaId>);
+/// The default notion of a system parachain: any `ParaId` which reports itself as such through `IsSystem`
+/// (i.e. those below the first user index).
+pub struct SystemParachains;
+impl Contains<ParaId> for SystemParachains {
+	fn contains(id: &ParaId) -> bool {
+		id.is_system()
+	}
+}
+
+/// Matches any child parachain whose ID is contained in `ParaIdRange`, e.g. `SystemParachains`.
+pub struct IsChildSystemParachain<ParaIdRange>(PhantomData<ParaIdRange>);
 impl<
-	ParaId: IsSystem + From<u32>,
-> Contains<MultiLocation> for IsChildSystemParachain<ParaId> {
+	ParaIdRange: Contains<ParaId>,
+> Contains<MultiLocation> for IsChildSystemParachain<ParaIdRange> {
 	fn contains(l: &MultiLocation) -> bool {
-		matches!(l, MultiLocation::X1(Junction::Parachain(id)) if ParaId::from(*id).is_system())
+		matches!(l, MultiLocation::X1(Junction::Parachain(id)) if ParaIdRange::contains(&ParaId::from(*id)))
 	}
 }
 
@@ -19,7 +19,7 @@ use xcm::v0::{Error as XcmError, Result, MultiAsset, MultiLocation};
 use sp_runtime::traits::{SaturatedConversion, CheckedSub};
 use frame_support::traits::{ExistenceRequirement::AllowDeath, WithdrawReasons, Get};
 use xcm_executor::traits::{MatchesFungible, Convert, TransactAsset};
-use xcm_executor::Assets;
+use xcm_executor::{Assets, XcmContext};
 
 /// Asset transaction errors.
 enum Error {
@@ -54,7 +54,7 @@ impl<
 	AccountId: Clone,	// can't get away without it since Currency is generic over it.
 	CheckedAccount: Get<Option<AccountId>>,
 > TransactAsset for CurrencyAdapter<Currency, Matcher, AccountIdConverter, AccountId, CheckedAccount> {
-	fn can_check_in(_origin: &MultiLocation, what: &MultiAsset) -> Result {
+	fn can_check_in(_origin: &MultiLocation, what: &MultiAsset, _context: &XcmContext) -> Result {
 		// Check we handle this asset.
 		let amount: Currency::Balance = Matcher::matches_fungible(what)
 			.ok_or(Error::AssetNotFound)?;
@@ -68,7 +68,7 @@ impl<
 		Ok(())
 	}
 
-	fn check_in(_origin: &MultiLocation, what: &MultiAsset) {
+	fn check_in(_origin: &MultiLocation, what: &MultiAsset, _context: &XcmContext) {
 		if let Some(amount) = Matcher::matches_fungible(what) {
 			if let Some(checked_account) = CheckedAccount::get() {
 				let ok = Currency::withdraw(&checked_account, amount, WithdrawReasons::TRANSFER, AllowDeath).is_ok();
@@ -77,7 +77,7 @@ impl<
 		}
 	}
 
-	fn check_out(_dest: &MultiLocation, what: &MultiAsset) {
+	fn check_out(_dest: &MultiLocation, what: &MultiAsset, _context: &XcmContext) {
 		if let Some(amount) = Matcher::matches_fungible(what) {
 			if let Some(checked_account) = CheckedAccount::get() {
 				Currency::deposit_creating(&checked_account, amount);
@@ -85,7 +85,7 @@ impl<
 		}
 	}
 
-	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> Result {
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation, _context: &XcmContext) -> Result {
 		// Check we handle this asset.
 		let amount: u128 = Matcher::matches_fungible(&what)
 			.ok_or(Error::AssetNotFound)?
@@ -101,7 +101,8 @@ impl<
 
 	fn withdraw_asset(
 		what: &MultiAsset,
-		who: &MultiLocation
+		who: &MultiLocation,
+		_context: &XcmContext,
 	) -> result::Result<Assets, XcmError> {
 		// Check we handle this asset.
 		let amount: u128 = Matcher::matches_fungible(what)
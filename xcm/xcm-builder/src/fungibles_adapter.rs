@@ -17,7 +17,7 @@
 use sp_std::{prelude::*, result, marker::PhantomData, borrow::Borrow};
 use xcm::v0::{Error as XcmError, Result, MultiAsset, MultiLocation, Junction};
 use frame_support::traits::{Get, tokens::fungibles, Contains};
-use xcm_executor::traits::{TransactAsset, Convert};
+use xcm_executor::{XcmContext, traits::{TransactAsset, Convert}};
 
 /// Asset transaction errors.
 pub enum Error {
@@ -147,6 +147,7 @@ impl<
 		what: &MultiAsset,
 		from: &MultiLocation,
 		to: &MultiLocation,
+		_context: &XcmContext,
 	) -> result::Result<xcm_executor::Assets, XcmError> {
 		// Check we handle this asset.
 		let (asset_id, amount) = Matcher::matches_fungibles(what)?;
@@ -171,7 +172,7 @@ impl<
 	CheckAsset: Contains<Assets::AssetId>,
 	CheckingAccount: Get<AccountId>,
 > TransactAsset for FungiblesMutateAdapter<Assets, Matcher, AccountIdConverter, AccountId, CheckAsset, CheckingAccount> {
-	fn can_check_in(_origin: &MultiLocation, what: &MultiAsset) -> Result {
+	fn can_check_in(_origin: &MultiLocation, what: &MultiAsset, _context: &XcmContext) -> Result {
 		// Check we handle this asset.
 		let (asset_id, amount) = Matcher::matches_fungibles(what)?;
 		if CheckAsset::contains(&asset_id) {
@@ -184,7 +185,7 @@ impl<
 		Ok(())
 	}
 
-	fn check_in(_origin: &MultiLocation, what: &MultiAsset) {
+	fn check_in(_origin: &MultiLocation, what: &MultiAsset, _context: &XcmContext) {
 		if let Ok((asset_id, amount)) = Matcher::matches_fungibles(what) {
 			if CheckAsset::contains(&asset_id) {
 				let checking_account = CheckingAccount::get();
@@ -194,7 +195,7 @@ impl<
 		}
 	}
 
-	fn check_out(_dest: &MultiLocation, what: &MultiAsset) {
+	fn check_out(_dest: &MultiLocation, what: &MultiAsset, _context: &XcmContext) {
 		if let Ok((asset_id, amount)) = Matcher::matches_fungibles(what) {
 			if CheckAsset::contains(&asset_id) {
 				let checking_account = CheckingAccount::get();
@@ -204,7 +205,7 @@ impl<
 		}
 	}
 
-	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> Result {
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation, _context: &XcmContext) -> Result {
 		// Check we handle this asset.
 		let (asset_id, amount) = Matcher::matches_fungibles(what)?;
 		let who = AccountIdConverter::convert_ref(who)
@@ -215,7 +216,8 @@ impl<
 
 	fn withdraw_asset(
 		what: &MultiAsset,
-		who: &MultiLocation
+		who: &MultiLocation,
+		_context: &XcmContext,
 	) -> result::Result<xcm_executor::Assets, XcmError> {
 		// Check we handle this asset.
 		let (asset_id, amount) = Matcher::matches_fungibles(what)?;
@@ -238,39 +240,42 @@ impl<
 	CheckAsset: Contains<Assets::AssetId>,
 	CheckingAccount: Get<AccountId>,
 > TransactAsset for FungiblesAdapter<Assets, Matcher, AccountIdConverter, AccountId, CheckAsset, CheckingAccount> {
-	fn can_check_in(origin: &MultiLocation, what: &MultiAsset) -> Result {
+	fn can_check_in(origin: &MultiLocation, what: &MultiAsset, context: &XcmContext) -> Result {
 		FungiblesMutateAdapter::<Assets, Matcher, AccountIdConverter, AccountId, CheckAsset, CheckingAccount>
-			::can_check_in(origin, what)
+			::can_check_in(origin, what, context)
 	}
 
-	fn check_in(origin: &MultiLocation, what: &MultiAsset) {
+	fn check_in(origin: &MultiLocation, what: &MultiAsset, context: &XcmContext) {
 		FungiblesMutateAdapter::<Assets, Matcher, AccountIdConverter, AccountId, CheckAsset, CheckingAccount>
-			::check_in(origin, what)
+			::check_in(origin, what, context)
 	}
 
-	fn check_out(dest: &MultiLocation, what: &MultiAsset) {
+	fn check_out(dest: &MultiLocation, what: &MultiAsset, context: &XcmContext) {
 		FungiblesMutateAdapter::<Assets, Matcher, AccountIdConverter, AccountId, CheckAsset, CheckingAccount>
-			::check_out(dest, what)
+			::check_out(dest, what, context)
 	}
 
-	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> Result {
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation, context: &XcmContext) -> Result {
 		FungiblesMutateAdapter::<Assets, Matcher, AccountIdConverter, AccountId, CheckAsset, CheckingAccount>
-			::deposit_asset(what, who)
+			::deposit_asset(what, who, context)
 	}
 
 	fn withdraw_asset(
 		what: &MultiAsset,
-		who: &MultiLocation
+		who: &MultiLocation,
+		context: &XcmContext,
 	) -> result::Result<xcm_executor::Assets, XcmError> {
 		FungiblesMutateAdapter::<Assets, Matcher, AccountIdConverter, AccountId, CheckAsset, CheckingAccount>
-			::withdraw_asset(what, who)
+			::withdraw_asset(what, who, context)
 	}
 
 	fn transfer_asset(
 		what: &MultiAsset,
 		from: &MultiLocation,
 		to: &MultiLocation,
+		context: &XcmContext,
 	) -> result::Result<xcm_executor::Assets, XcmError> {
-		FungiblesTransferAdapter::<Assets, Matcher, AccountIdConverter, AccountId>::transfer_asset(what, from, to)
+		FungiblesTransferAdapter::<Assets, Matcher, AccountIdConverter, AccountId>
+			::transfer_asset(what, from, to, context)
 	}
 }
@@ -31,13 +31,14 @@ mod origin_conversion;
 pub use origin_conversion::{
 	SovereignSignedViaLocation, ParentAsSuperuser, ChildSystemParachainAsSuperuser, SiblingSystemParachainAsSuperuser,
 	ChildParachainAsNative, SiblingParachainAsNative, RelayChainAsNative, SignedAccountId32AsNative,
-	SignedAccountKey20AsNative, EnsureXcmOrigin, SignedToAccountId32, BackingToPlurality,
+	SignedAccountKey20AsNative, EnsureXcmOrigin, SignedToAccountId32, SignedToAccountKey20, BackingToPlurality,
+	OriginToPluralityVoice, OriginKindFilter,
 };
 
 mod barriers;
 pub use barriers::{
 	TakeWeightCredit, AllowUnpaidExecutionFrom, AllowTopLevelPaidExecutionFrom, AllowKnownQueryResponses,
-	IsChildSystemParachain,
+	IsChildSystemParachain, SystemParachains,
 };
 
 mod currency_adapter;
@@ -47,10 +48,17 @@ mod fungibles_adapter;
 pub use fungibles_adapter::FungiblesAdapter;
 
 mod weight;
-pub use weight::{FixedRateOfConcreteFungible, FixedWeightBounds, UsingComponents};
+pub use weight::{
+	FixedRateOfConcreteFungible, FixedWeightBounds, TakeRevenue, UsingComponents, WeightInfoBounds, XcmWeightInfo,
+};
 
 mod matches_fungible;
 pub use matches_fungible::{IsAbstract, IsConcrete};
 
 mod filter_asset_location;
 pub use filter_asset_location::{Case, NativeAsset};
+
+#[cfg(feature = "std")]
+mod ss58;
+#[cfg(feature = "std")]
+pub use ss58::{Ss58Error, account_id32_from_ss58, account_id32_to_ss58};
@@ -108,11 +108,11 @@ impl<
 	Network: Get<NetworkId>,
 	AccountId: From<[u8; 32]> + Into<[u8; 32]> + Clone,
 > Convert<MultiLocation, AccountId> for AccountId32Aliases<Network, AccountId> {
-	fn convert(location: MultiLocation) -> Result<AccountId, MultiLocation> {
-		let id = match location {
-			MultiLocation::X1(Junction::AccountId32 { id, network: NetworkId::Any }) => id,
-			MultiLocation::X1(Junction::AccountId32 { id, network }) if &network == &Network::get() => id,
-			l => return Err(l),
+	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<AccountId, ()> {
+		let id = match location.borrow() {
+			MultiLocation::X1(Junction::AccountId32 { id, network: NetworkId::Any }) => *id,
+			MultiLocation::X1(Junction::AccountId32 { id, network }) if network == &Network::get() => *id,
+			_ => return Err(()),
 		};
 		Ok(id.into())
 	}
@@ -127,11 +127,11 @@ impl<
 	Network: Get<NetworkId>,
 	AccountId: From<[u8; 20]> + Into<[u8; 20]> + Clone,
 > Convert<MultiLocation, AccountId> for AccountKey20Aliases<Network, AccountId> {
-	fn convert(location: MultiLocation) -> Result<AccountId, MultiLocation> {
-		let key = match location {
-			MultiLocation::X1(Junction::AccountKey20 { key, network: NetworkId::Any }) => key,
-			MultiLocation::X1(Junction::AccountKey20 { key, network }) if &network == &Network::get() => key,
-			l => return Err(l),
+	fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<AccountId, ()> {
+		let key = match location.borrow() {
+			MultiLocation::X1(Junction::AccountKey20 { key, network: NetworkId::Any }) => *key,
+			MultiLocation::X1(Junction::AccountKey20 { key, network }) if network == &Network::get() => *key,
+			_ => return Err(()),
 		};
 		Ok(key.into())
 	}
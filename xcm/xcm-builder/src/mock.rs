@@ -29,11 +29,13 @@ pub use frame_support::{
 	traits::{Get, Contains, IsInVec},
 };
 pub use xcm_executor::{
-	Assets, Config, traits::{TransactAsset, ConvertOrigin, FilterAssetLocation, InvertLocation, OnResponse}
+	Assets, Config, XcmContext, traits::{
+		TransactAsset, ConvertOrigin, FilterAssetLocation, InvertLocation, OnResponse, AssetExchange,
+	}
 };
 pub use crate::{
 	TakeWeightCredit, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, FixedWeightBounds,
-	FixedRateOfConcreteFungible, AllowKnownQueryResponses, LocationInverter,
+	FixedRateOfConcreteFungible, AllowKnownQueryResponses, LocationInverter, TakeRevenue,
 };
 
 pub enum TestOrigin { Root, Relay, Signed(u64), Parachain(u32) }
@@ -109,6 +111,7 @@ impl SendXcm for TestSendXcm {
 
 thread_local! {
 	pub static ASSETS: RefCell<BTreeMap<u64, Assets>> = RefCell::new(BTreeMap::new());
+	pub static TRANSACT_CONTEXTS: RefCell<Vec<XcmContext>> = RefCell::new(Vec::new());
 }
 pub fn assets(who: u64) -> Vec<MultiAsset> {
 	ASSETS.with(|a| a.borrow().get(&who).map_or(vec![], |a| a.clone().into()))
@@ -121,15 +124,21 @@ pub fn add_asset(who: u64, what: MultiAsset) {
 	);
 }
 
+pub fn transact_contexts() -> Vec<XcmContext> {
+	TRANSACT_CONTEXTS.with(|c| c.borrow().clone())
+}
+
 pub struct TestAssetTransactor;
 impl TransactAsset for TestAssetTransactor {
-	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> Result<(), XcmError> {
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation, context: &XcmContext) -> Result<(), XcmError> {
+		TRANSACT_CONTEXTS.with(|c| c.borrow_mut().push(context.clone()));
 		let who = to_account(who.clone()).map_err(|_| XcmError::LocationCannotHold)?;
 		add_asset(who, what.clone());
 		Ok(())
 	}
 
-	fn withdraw_asset(what: &MultiAsset, who: &MultiLocation) -> Result<Assets, XcmError> {
+	fn withdraw_asset(what: &MultiAsset, who: &MultiLocation, context: &XcmContext) -> Result<Assets, XcmError> {
+		TRANSACT_CONTEXTS.with(|c| c.borrow_mut().push(context.clone()));
 		let who = to_account(who.clone()).map_err(|_| XcmError::LocationCannotHold)?;
 		ASSETS.with(|a| a.borrow_mut()
 			.get_mut(&who)
@@ -242,6 +251,34 @@ pub fn response(query_id: u64) -> Option<Response> {
 	)
 }
 
+thread_local! {
+	pub static EXCHANGE_ASSETS: RefCell<Assets> = RefCell::new(Assets::new());
+}
+pub fn set_exchange_assets(assets: impl Into<Assets>) {
+	EXCHANGE_ASSETS.with(|a| a.replace(assets.into()));
+}
+pub fn exchange_assets() -> Vec<MultiAsset> {
+	EXCHANGE_ASSETS.with(|a| a.borrow().clone().into())
+}
+/// A constant-rate exchange: whatever is asked for is handed out of the pool, provided the pool holds it, and
+/// everything given is added to the pool.
+pub struct TestAssetExchange;
+impl AssetExchange for TestAssetExchange {
+	fn exchange_asset(_origin: Option<&MultiLocation>, give: Assets, want: &[MultiAsset]) -> Result<Assets, Assets> {
+		let mut have = EXCHANGE_ASSETS.with(|a| a.borrow().clone());
+		let mut received = Assets::new();
+		for asset in want.iter() {
+			match have.try_take(asset.clone()) {
+				Ok(taken) => received.saturating_subsume_all(taken),
+				Err(()) => return Err(give),
+			}
+		}
+		have.saturating_subsume_all(give);
+		EXCHANGE_ASSETS.with(|a| a.replace(have));
+		Ok(received)
+	}
+}
+
 parameter_types! {
 	pub TestAncestry: MultiLocation = X1(Parachain(42));
 	pub UnitWeightCost: Weight = 10;
@@ -254,6 +291,20 @@ parameter_types! {
 	pub static WeightPrice: (MultiLocation, u128) = (Null, 1_000_000_000_000);
 }
 
+thread_local! {
+	pub static REVENUE: RefCell<Vec<(MultiAsset, XcmContext)>> = RefCell::new(Vec::new());
+}
+/// Each revenue taken by the trader, with the context of the message which paid it.
+pub fn revenue() -> Vec<(MultiAsset, XcmContext)> {
+	REVENUE.with(|r| r.borrow().clone())
+}
+pub struct TestRevenue;
+impl TakeRevenue for TestRevenue {
+	fn take_revenue(revenue: MultiAsset, context: &XcmContext) {
+		REVENUE.with(|r| r.borrow_mut().push((revenue, context.clone())));
+	}
+}
+
 pub type TestBarrier = (
 	TakeWeightCredit,
 	AllowKnownQueryResponses<TestResponseHandler>,
@@ -272,6 +323,7 @@ impl Config for TestConfig {
 	type LocationInverter = LocationInverter<TestAncestry>;
 	type Barrier = TestBarrier;
 	type Weigher = FixedWeightBounds<UnitWeightCost, TestCall>;
-	type Trader = FixedRateOfConcreteFungible<WeightPrice, ()>;
+	type Trader = FixedRateOfConcreteFungible<WeightPrice, TestRevenue>;
 	type ResponseHandler = TestResponseHandler;
+	type AssetExchanger = TestAssetExchange;
 }
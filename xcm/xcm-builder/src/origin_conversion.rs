@@ -17,9 +17,9 @@
 use sp_std::{marker::PhantomData, convert::TryInto};
 use xcm::v0::{MultiLocation, OriginKind, NetworkId, Junction, BodyId, BodyPart};
 use xcm_executor::traits::{Convert, ConvertOrigin};
-use frame_support::traits::{EnsureOrigin, Get, OriginTrait, GetBacking};
+use frame_support::traits::{EnsureOrigin, Get, OriginTrait, GetBacking, Backing, Contains};
 use frame_system::RawOrigin as SystemRawOrigin;
-use polkadot_parachain::primitives::IsSystem;
+use polkadot_parachain::primitives::Id as ParaId;
 
 /// Sovereign accounts use the system's `Signed` origin with an account ID derived from the
 /// `LocationConverter`.
@@ -40,6 +40,33 @@ impl<
 	}
 }
 
+/// Restricts the `OriginKind`s which each origin may ask `Inner` to convert into.
+///
+/// The `origin_type` of a `Transact` is chosen by the sender, so without this the kinds a location can obtain are
+/// decided only by which converters happen to be configured. A conversion is attempted only if `Policy` contains the
+/// pair of origin location and requested kind; otherwise the origin is returned unconverted, which the executor
+/// reports as `BadOrigin`.
+pub struct OriginKindFilter<Inner, Policy>(PhantomData<(Inner, Policy)>);
+impl<
+	Origin,
+	Inner: ConvertOrigin<Origin>,
+	Policy: Contains<(MultiLocation, OriginKind)>,
+> ConvertOrigin<Origin> for OriginKindFilter<Inner, Policy> {
+	fn convert_origin(origin: MultiLocation, kind: OriginKind) -> Result<Origin, MultiLocation> {
+		let query = (origin, kind);
+		if Policy::contains(&query) {
+			Inner::convert_origin(query.0, kind)
+		} else {
+			Err(query.0)
+		}
+	}
+}
+
+/// Converts the bare parent location (`X1(Parent)`) with `OriginKind::Superuser` into the root origin.
+///
+/// Only the undescended parent matches. A location *within* the parent, such as a user account on the relay
+/// chain (`X2(Parent, AccountId32 { .. })`) or a sibling parachain (`X2(Parent, Parachain(..))`), can ask for a
+/// `Superuser` origin just as easily, and must not be granted root merely because it is routed via the parent.
 pub struct ParentAsSuperuser<Origin>(PhantomData<Origin>);
 impl<
 	Origin: OriginTrait,
@@ -53,30 +80,34 @@ impl<
 	}
 }
 
-pub struct ChildSystemParachainAsSuperuser<ParaId, Origin>(PhantomData<(ParaId, Origin)>);
+/// Converts a `Superuser` request from a child parachain into the root origin, provided its ID is contained in
+/// `ParaIdRange` (typically `SystemParachains`).
+pub struct ChildSystemParachainAsSuperuser<ParaIdRange, Origin>(PhantomData<(ParaIdRange, Origin)>);
 impl<
-	ParaId: IsSystem + From<u32>,
+	ParaIdRange: Contains<ParaId>,
 	Origin: OriginTrait,
-> ConvertOrigin<Origin> for ChildSystemParachainAsSuperuser<ParaId, Origin> {
+> ConvertOrigin<Origin> for ChildSystemParachainAsSuperuser<ParaIdRange, Origin> {
 	fn convert_origin(origin: MultiLocation, kind: OriginKind) -> Result<Origin, MultiLocation> {
 		match (kind, origin) {
 			(OriginKind::Superuser, MultiLocation::X1(Junction::Parachain(id)))
-			if ParaId::from(id).is_system() =>
+			if ParaIdRange::contains(&ParaId::from(id)) =>
 				Ok(Origin::root()),
 			(_, origin) => Err(origin),
 		}
 	}
 }
 
-pub struct SiblingSystemParachainAsSuperuser<ParaId, Origin>(PhantomData<(ParaId, Origin)>);
+/// Converts a `Superuser` request from a sibling parachain into the root origin, provided its ID is contained in
+/// `ParaIdRange` (typically `SystemParachains`).
+pub struct SiblingSystemParachainAsSuperuser<ParaIdRange, Origin>(PhantomData<(ParaIdRange, Origin)>);
 impl<
-	ParaId: IsSystem + From<u32>,
-	Origin: OriginTrait
-> ConvertOrigin<Origin> for SiblingSystemParachainAsSuperuser<ParaId, Origin> {
+	ParaIdRange: Contains<ParaId>,
+	Origin: OriginTrait,
+> ConvertOrigin<Origin> for SiblingSystemParachainAsSuperuser<ParaIdRange, Origin> {
 	fn convert_origin(origin: MultiLocation, kind: OriginKind) -> Result<Origin, MultiLocation> {
 		match (kind, origin) {
 			(OriginKind::Superuser, MultiLocation::X2(Junction::Parent, Junction::Parachain(id)))
-			if ParaId::from(id).is_system() =>
+			if ParaIdRange::contains(&ParaId::from(id)) =>
 				Ok(Origin::root()),
 			(_, origin) => Err(origin),
 		}
@@ -172,6 +203,9 @@ impl<
 }
 
 /// EnsureOrigin barrier to convert from dispatch origin to XCM origin, if one exists.
+///
+/// `Conversion` may be a tuple of converters, e.g. `(SignedToAccountId32<..>, BackingToPlurality<..>)`, to accept
+/// several kinds of local origin. They are tried in order and the first to succeed decides the location.
 pub struct EnsureXcmOrigin<Origin, Conversion>(PhantomData<(Origin, Conversion)>);
 impl<
 	Origin: OriginTrait + Clone,
@@ -225,6 +259,32 @@ impl<
 	}
 }
 
+/// `Convert` implementation to convert from a `Signed` (system) `Origin` into an `AccountKey20`.
+///
+/// Typically used when configuring `pallet-xcm` on chains with 20-byte accounts (such as `H160`), which cannot use
+/// `SignedToAccountId32`. `AccountId` must convert into exactly 20 bytes, so longer account types are rejected at
+/// compile time rather than truncated.
+pub struct SignedToAccountKey20<Origin, AccountId, Network>(
+	PhantomData<(Origin, AccountId, Network)>
+);
+impl<
+	Origin: OriginTrait + Clone,
+	AccountId: Into<[u8; 20]>,
+	Network: Get<NetworkId>,
+> Convert<Origin, MultiLocation> for SignedToAccountKey20<Origin, AccountId, Network> where
+	Origin::PalletsOrigin: From<SystemRawOrigin<AccountId>> +
+	TryInto<SystemRawOrigin<AccountId>, Error=Origin::PalletsOrigin>
+{
+	fn convert(o: Origin) -> Result<MultiLocation, Origin> {
+		o.try_with_caller(|caller| match caller.try_into() {
+			Ok(SystemRawOrigin::Signed(who)) =>
+				Ok(Junction::AccountKey20 { network: Network::get(), key: who.into() }.into()),
+			Ok(other) => Err(other.into()),
+			Err(other) => Err(other),
+		})
+	}
+}
+
 /// `Convert` implementation to convert from some an origin which implements `Backing` into a corresponding `Plurality`
 /// MultiLocation.
 ///
@@ -243,14 +303,43 @@ impl<
 {
 	fn convert(o: Origin) -> Result<MultiLocation, Origin> {
 		o.try_with_caller(|caller| match caller.try_into() {
-			Ok(co) => match co.get_backing() {
-				Some(backing) => Ok(Junction::Plurality {
-					id: Body::get(),
-					part: BodyPart::Fraction { nom: backing.approvals, denom: backing.eligible },
-				}.into()),
+			Ok(co) => match co.get_backing().and_then(backing_fraction) {
+				Some(part) => Ok(Junction::Plurality { id: Body::get(), part }.into()),
 				None => Err(co.into()),
 			}
 			Err(other) => Err(other),
 		})
 	}
 }
+
+/// The `BodyPart::Fraction` of a body represented by `backing`.
+///
+/// Returns `None` if the backing is meaningless, i.e. nobody is eligible or there are more approvals than eligible
+/// members, since no sensible proportion of the body can be claimed from it.
+pub(crate) fn backing_fraction(backing: Backing) -> Option<BodyPart> {
+	if backing.eligible == 0 || backing.approvals > backing.eligible {
+		return None;
+	}
+	Some(BodyPart::Fraction { nom: backing.approvals, denom: backing.eligible })
+}
+
+/// `Convert` implementation to convert from an origin which passes the check of an `EnsureOrigin` into a voice of a
+/// given pluralistic `Body`.
+///
+/// Typically used when configuring `pallet-xcm` for allowing a collective's approval, however reached, to dispatch an
+/// XCM as the `Plurality` of that body with `BodyPart::Voice`.
+pub struct OriginToPluralityVoice<Origin, EnsureBodyOrigin, Body>(
+	PhantomData<(Origin, EnsureBodyOrigin, Body)>
+);
+impl<
+	Origin: OriginTrait + Clone,
+	EnsureBodyOrigin: EnsureOrigin<Origin>,
+	Body: Get<BodyId>,
+> Convert<Origin, MultiLocation> for OriginToPluralityVoice<Origin, EnsureBodyOrigin, Body> {
+	fn convert(o: Origin) -> Result<MultiLocation, Origin> {
+		match EnsureBodyOrigin::try_origin(o) {
+			Ok(_) => Ok(Junction::Plurality { id: Body::get(), part: BodyPart::Voice }.into()),
+			Err(o) => Err(o),
+		}
+	}
+}
@@ -0,0 +1,59 @@
+// Copyright 2021 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! SS58 helpers for `AccountId32` junctions. These are only available with `std`, for use by tests and tooling.
+
+use std::convert::TryFrom;
+use sp_core::crypto::{Ss58Codec, Ss58AddressFormat, PublicError};
+use sp_runtime::AccountId32;
+use xcm::v0::{Junction, NetworkId};
+
+/// Error returned when an SS58 address cannot be turned into an `AccountId32` junction.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Ss58Error {
+	/// The address decoded, but its checksum does not match.
+	BadChecksum,
+	/// The address is not valid SS58 for a 32-byte account.
+	Malformed(PublicError),
+}
+
+impl From<PublicError> for Ss58Error {
+	fn from(e: PublicError) -> Self {
+		match e {
+			PublicError::InvalidChecksum => Ss58Error::BadChecksum,
+			e => Ss58Error::Malformed(e),
+		}
+	}
+}
+
+/// Build an `AccountId32` junction on `network` from an SS58 `address` of any prefix.
+pub fn account_id32_from_ss58(address: &str, network: NetworkId) -> Result<Junction, Ss58Error> {
+	let (account, _) = AccountId32::from_ss58check_with_version(address)?;
+	Ok(Junction::AccountId32 { network, id: account.into() })
+}
+
+/// Render the id of an `AccountId32` junction as an SS58 address with the given `prefix`.
+///
+/// Returns `None` if `junction` is not an `AccountId32` or `prefix` is not a valid SS58 prefix.
+pub fn account_id32_to_ss58(junction: &Junction, prefix: u16) -> Option<String> {
+	match junction {
+		Junction::AccountId32 { id, .. } => {
+			let format = Ss58AddressFormat::try_from(prefix).ok()?;
+			Some(AccountId32::from(*id).to_ss58check_with_version(format))
+		}
+		_ => None,
+	}
+}
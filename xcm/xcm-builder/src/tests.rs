@@ -49,6 +49,51 @@ fn weigher_should_work() {
 	assert_eq!(<TestConfig as Config>::Weigher::shallow(&mut message), Ok(30));
 }
 
+#[test]
+fn weigher_should_count_repeated_and_nested_instructions() {
+	type Weigher = <TestConfig as Config>::Weigher;
+
+	// 100 identical orders cost a unit each, on top of the unit for the message itself.
+	let mut message = Xcm::<TestCall>::WithdrawAsset {
+		assets: vec![ConcreteFungible { id: X1(Parent), amount: 100 }],
+		effects: vec![Order::DepositAsset { assets: vec![All], dest: Null }; 100],
+	};
+	assert_eq!(Weigher::shallow(&mut message), Ok(1010));
+	assert_eq!(Weigher::deep(&mut message), Ok(0));
+	assert_eq!(Weigher::weight(&mut message), Ok(1010));
+
+	let mut message = Xcm::<TestCall>::TeleportAsset {
+		assets: vec![ConcreteFungible { id: X1(Parent), amount: 100 }],
+		effects: vec![
+			Order::BuyExecution { fees: All, weight: 0, debt: 30, halt_on_error: true, xcm: vec![
+				Xcm::Transact {
+					origin_type: OriginKind::Native,
+					require_weight_at_most: 50,
+					call: TestCall::Any(50, None).encode().into(),
+				},
+				Xcm::WithdrawAsset {
+					assets: vec![All],
+					effects: vec![
+						Order::BuyExecution { fees: All, weight: 0, debt: 0, halt_on_error: true, xcm: vec![
+							Xcm::TransferAsset { assets: vec![All], dest: Null },
+						] },
+					],
+				},
+			] },
+		],
+	};
+	// Shallow: the message and its single order.
+	assert_eq!(Weigher::shallow(&mut message), Ok(20));
+	// Deep: `Transact` (50 + 10), then the inner `WithdrawAsset` (20) and its own nested `TransferAsset` (10).
+	assert_eq!(Weigher::deep(&mut message), Ok(90));
+	assert_eq!(Weigher::weight(&mut message), Ok(110));
+
+	let mut message = Xcm::<TestCall>::RelayedFrom { who: X1(Parent), message: Box::new(message) };
+	assert_eq!(Weigher::shallow(&mut message), Ok(30));
+	assert_eq!(Weigher::deep(&mut message), Ok(90));
+	assert_eq!(Weigher::weight(&mut message), Ok(120));
+}
+
 #[test]
 fn take_weight_credit_barrier_should_work() {
 	let mut message = opaque::Xcm::TransferAsset {
@@ -307,10 +352,15 @@ fn paid_transacting_should_refund_payment_for_unused_weight() {
 			Order::<TestCall>::DepositAsset { assets: vec![ All ], dest: one.clone() },
 		],
 	};
+	let message_hash = xcm::VersionedXcm::from(message.clone()).hash();
 	let weight_limit = 100;
-	let r = XcmExecutor::<TestConfig>::execute_xcm(origin, message, weight_limit);
+	let r = XcmExecutor::<TestConfig>::execute_xcm(origin.clone(), message, weight_limit);
 	assert_eq!(r, Outcome::Complete(50));
 	assert_eq!(assets(1), vec![ ConcreteFungible { id: X1(Parent), amount: 50 } ]);
+
+	// Only what was paid for the weight actually used is revenue, and it is attributed to the paying message.
+	let context = XcmContext { origin: Some(origin), message_hash, topic: None };
+	assert_eq!(revenue(), vec![ (ConcreteFungible { id: X1(Parent), amount: 50 }, context) ]);
 }
 
 #[test]
@@ -336,3 +386,590 @@ fn prepaid_result_of_query_should_get_free_execution() {
 	let r = XcmExecutor::<TestConfig>::execute_xcm(origin.clone(), message.clone(), weight_limit);
 	assert_eq!(r, Outcome::Incomplete(10, XcmError::Barrier));
 }
+
+#[test]
+fn exchange_asset_should_work() {
+	AllowUnpaidFrom::set(vec![ X1(Parent) ]);
+	add_reserve(X1(Parent), AllConcreteFungible { id: X1(Parent) });
+	// The exchange holds 100 of our native asset.
+	set_exchange_assets(vec![ ConcreteFungible { id: Null, amount: 100 } ]);
+	let one = X1(AccountIndex64 { index: 1, network: Any });
+
+	// The relay chain deposits 100 of its token and swaps half of it for 25 of our native asset.
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		X1(Parent),
+		Xcm::ReserveAssetDeposit {
+			assets: vec![ ConcreteFungible { id: X1(Parent), amount: 100 } ],
+			effects: vec![
+				Order::ExchangeAsset {
+					give: vec![ ConcreteFungible { id: X1(Parent), amount: 50 } ],
+					receive: vec![ ConcreteFungible { id: Null, amount: 25 } ],
+				},
+				Order::DepositAsset { assets: vec![ All ], dest: one.clone() },
+			],
+		},
+		50,
+	);
+	assert_eq!(r, Outcome::Complete(30));
+	assert_eq!(assets(1), vec![
+		ConcreteFungible { id: Null, amount: 25 },
+		ConcreteFungible { id: X1(Parent), amount: 50 },
+	]);
+	assert_eq!(exchange_assets(), vec![
+		ConcreteFungible { id: Null, amount: 75 },
+		ConcreteFungible { id: X1(Parent), amount: 50 },
+	]);
+}
+
+#[test]
+fn exchange_asset_without_liquidity_should_fail() {
+	AllowUnpaidFrom::set(vec![ X1(Parent) ]);
+	add_reserve(X1(Parent), AllConcreteFungible { id: X1(Parent) });
+	// Not enough of our native asset to satisfy the order.
+	set_exchange_assets(vec![ ConcreteFungible { id: Null, amount: 10 } ]);
+	let one = X1(AccountIndex64 { index: 1, network: Any });
+
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		X1(Parent),
+		Xcm::ReserveAssetDeposit {
+			assets: vec![ ConcreteFungible { id: X1(Parent), amount: 100 } ],
+			effects: vec![
+				Order::ExchangeAsset {
+					give: vec![ ConcreteFungible { id: X1(Parent), amount: 50 } ],
+					receive: vec![ ConcreteFungible { id: Null, amount: 25 } ],
+				},
+				Order::DepositAsset { assets: vec![ All ], dest: one },
+			],
+		},
+		50,
+	);
+	assert_eq!(r, Outcome::Incomplete(30, XcmError::NoDeal));
+	assert_eq!(assets(1), vec![]);
+	assert_eq!(exchange_assets(), vec![ ConcreteFungible { id: Null, amount: 10 } ]);
+}
+
+#[test]
+fn reserve_transfer_without_funds_should_not_send() {
+	AllowUnpaidFrom::set(vec![ X1(Parachain(1)) ]);
+	// Child parachain #1 only owns 50 tokens held by us in reserve.
+	add_asset(1001, ConcreteFungible { id: Null, amount: 50 });
+	let three = X1(AccountIndex64{index:3, network:Any});
+
+	let r = XcmExecutor::<TestConfig>::execute_xcm(
+		X1(Parachain(1)),
+		Xcm::TransferReserveAsset {
+			assets: vec![ ConcreteFungible { id: Null, amount: 100 } ],
+			dest: X1(Parachain(2)),
+			effects: vec![ Order::DepositAsset { assets: vec![ All ], dest: three } ],
+		},
+		50,
+	);
+	assert_eq!(r, Outcome::Incomplete(10, XcmError::NotWithdrawable));
+	// Nothing moved and, crucially, nothing was announced to the destination.
+	assert_eq!(assets(1001), vec![ ConcreteFungible { id: Null, amount: 50 } ]);
+	assert_eq!(assets(1002), vec![]);
+	assert_eq!(sent_xcm(), vec![]);
+}
+
+#[test]
+fn location_converters_agree_by_value_and_by_reference() {
+	use sp_runtime::AccountId32 as AccountId;
+	use polkadot_parachain::primitives::Id as ParaId;
+	use xcm::v0::NetworkId::{self, Kusama, Polkadot};
+	use crate::{
+		Account32Hash, AccountId32Aliases, AccountKey20Aliases, ChildParachainConvertsVia, ParentIsDefault,
+		SiblingParachainConvertsVia,
+	};
+
+	frame_support::parameter_types! {
+		pub const KusamaNetwork: NetworkId = Kusama;
+	}
+
+	fn check<C: Convert<MultiLocation, A>, A: Clone + PartialEq + Debug>(location: &MultiLocation) -> Option<A> {
+		let by_ref = C::convert_ref(location).ok();
+		match C::convert(location.clone()) {
+			Ok(by_value) => assert_eq!(Some(by_value), by_ref),
+			// Ownership of the location must be handed back untouched on failure.
+			Err(returned) => { assert_eq!(&returned, location); assert_eq!(by_ref, None) },
+		}
+		by_ref
+	}
+
+	type Converters = (
+		ParentIsDefault<AccountId>,
+		ChildParachainConvertsVia<ParaId, AccountId>,
+		SiblingParachainConvertsVia<ParaId, AccountId>,
+		AccountId32Aliases<KusamaNetwork, AccountId>,
+		Account32Hash<KusamaNetwork, AccountId>,
+	);
+
+	let locations = vec![
+		Null,
+		X1(Parent),
+		X1(Parachain(1)),
+		X2(Parent, Parachain(1)),
+		X1(AccountId32 { network: Any, id: [1; 32] }),
+		X1(AccountId32 { network: Kusama, id: [1; 32] }),
+		X1(AccountId32 { network: Polkadot, id: [1; 32] }),
+		X1(AccountKey20 { network: Any, key: [2; 20] }),
+		X1(AccountKey20 { network: Kusama, key: [2; 20] }),
+		X1(AccountKey20 { network: Polkadot, key: [2; 20] }),
+		X8(Parent, Parent, Parachain(1), PalletInstance(2), GeneralIndex { id: 3 }, OnlyChild,
+			GeneralKey(vec![4; 32]), AccountId32 { network: Any, id: [5; 32] }),
+	];
+	for location in &locations {
+		let parent = check::<ParentIsDefault<AccountId>, _>(location);
+		let child = check::<ChildParachainConvertsVia<ParaId, AccountId>, _>(location);
+		let sibling = check::<SiblingParachainConvertsVia<ParaId, AccountId>, _>(location);
+		let alias = check::<AccountId32Aliases<KusamaNetwork, AccountId>, _>(location);
+		let hash = check::<Account32Hash<KusamaNetwork, AccountId>, _>(location);
+		check::<AccountKey20Aliases<KusamaNetwork, [u8; 20]>, _>(location);
+		// The tuple yields the result of its first successful element.
+		let first = parent.or(child).or(sibling).or(alias).or(hash);
+		assert_eq!(check::<Converters, _>(location), first);
+	}
+
+	let alice = AccountId::from([1; 32]);
+	assert_eq!(check::<Converters, _>(&locations[4]), Some(alice.clone()));
+	assert_eq!(check::<Converters, _>(&locations[5]), Some(alice.clone()));
+	assert_ne!(check::<Converters, _>(&locations[6]), Some(alice));
+	assert_eq!(check::<AccountKey20Aliases<KusamaNetwork, [u8; 20]>, _>(&locations[8]), Some([2; 20]));
+	assert_eq!(check::<AccountKey20Aliases<KusamaNetwork, [u8; 20]>, _>(&locations[9]), None);
+}
+
+mod capture {
+	use std::{cell::RefCell, sync::Once};
+
+	/// A captured log record: its target, level and formatted message.
+	#[derive(Debug)]
+	pub struct Record {
+		pub target: String,
+		pub level: log::Level,
+		pub message: String,
+	}
+
+	thread_local! {
+		static RECORDS: RefCell<Option<Vec<Record>>> = RefCell::new(None);
+	}
+
+	struct CapturingLogger;
+	impl log::Log for CapturingLogger {
+		fn enabled(&self, metadata: &log::Metadata) -> bool { metadata.target().starts_with("xcm::") }
+		fn log(&self, record: &log::Record) {
+			if self.enabled(record.metadata()) {
+				RECORDS.with(|r| if let Some(records) = r.borrow_mut().as_mut() {
+					records.push(Record {
+						target: record.target().to_owned(),
+						level: record.level(),
+						message: record.args().to_string(),
+					});
+				});
+			}
+		}
+		fn flush(&self) {}
+	}
+
+	/// Run `f`, returning every `xcm::` log record it emitted on this thread.
+	pub fn capture(f: impl FnOnce()) -> Vec<Record> {
+		static INIT: Once = Once::new();
+		INIT.call_once(|| {
+			log::set_boxed_logger(Box::new(CapturingLogger)).expect("no other logger is installed in tests");
+			log::set_max_level(log::LevelFilter::Trace);
+		});
+		RECORDS.with(|r| *r.borrow_mut() = Some(Vec::new()));
+		f();
+		RECORDS.with(|r| r.borrow_mut().take().unwrap_or_default())
+	}
+}
+
+#[test]
+fn executor_should_log_downward_reserve_deposit() {
+	use xcm_executor::log_target::*;
+	use log::Level::{Debug, Trace};
+
+	AllowPaidFrom::set(vec![ X1(Parent) ]);
+	add_reserve(X1(Parent), AllConcreteFungible { id: X1(Parent) });
+	WeightPrice::set((X1(Parent), 1_000_000_000_000));
+
+	let message = Xcm::<TestCall>::ReserveAssetDeposit {
+		assets: vec![ ConcreteFungible { id: X1(Parent), amount: 100 } ],
+		effects: vec![
+			Order::<TestCall>::BuyExecution { fees: All, weight: 0, debt: 30, halt_on_error: true, xcm: vec![] },
+			Order::<TestCall>::DepositAsset { assets: vec![ All ], dest: Null },
+		],
+	};
+	let message_hash = xcm::VersionedXcm::from(message.clone()).hash();
+	let records = capture::capture(|| {
+		let r = XcmExecutor::<TestConfig>::execute_xcm(X1(Parent), message, 50);
+		assert_eq!(r, Outcome::Complete(30));
+	});
+	let find = |target: &str, level, prefix: &str| records.iter()
+		.find(|r| r.target == target && r.level == level && r.message.starts_with(prefix))
+		.unwrap_or_else(|| panic!("no {} record starting {:?} in {:#?}", target, prefix, records));
+
+	// The entry trace gives the origin and limits; the exit trace gives the outcome.
+	let entry = &records[0];
+	assert_eq!((entry.target.as_str(), entry.level), (EXECUTE, Trace));
+	assert!(entry.message.starts_with("execute_xcm_in_credit origin: X1(Parent), message: "), "{}", entry.message);
+	assert!(entry.message.ends_with("weight_limit: 50, weight_credit: 0"), "{}", entry.message);
+	let exit = records.last().unwrap();
+	assert_eq!(exit.message, "execute_xcm_in_credit outcome: Complete(30)");
+
+	// The message is hashed only once it has passed the barrier.
+	find(EXECUTE, Trace, &format!("do_execute_xcm origin: X1(Parent), hash: {:?}", message_hash));
+
+	// Buying the weight, and depositing what was left after paying for it, are each logged with their amounts.
+	find(TRADER, Trace, "buy_weight weight: 30, max_fee: ");
+	let deposit = find(TRANSACTOR, Debug, "deposit_asset asset: ");
+	assert!(deposit.message.ends_with("who: Null"), "{}", deposit.message);
+	assert!(records.iter().all(|r| r.target != BARRIERS), "the barrier passed the message");
+}
+
+#[test]
+fn ss58_account_id32_junctions_round_trip() {
+	use xcm::v0::NetworkId::{Kusama, Polkadot};
+
+	let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+	let alice_id = hex_literal::hex!["d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"];
+	let junction = account_id32_from_ss58(alice, Kusama).unwrap();
+	assert_eq!(junction, AccountId32 { network: Kusama, id: alice_id });
+	assert_eq!(account_id32_to_ss58(&junction, 42).as_deref(), Some(alice));
+
+	let bob = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+	let bob_id = hex_literal::hex!["8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"];
+	let junction = account_id32_from_ss58(bob, Polkadot).unwrap();
+	assert_eq!(junction, AccountId32 { network: Polkadot, id: bob_id });
+	assert_eq!(account_id32_to_ss58(&junction, 42).as_deref(), Some(bob));
+
+	// The same account under a different prefix decodes to the same id.
+	let bob_polkadot = account_id32_to_ss58(&junction, 0).unwrap();
+	assert_ne!(bob_polkadot, bob);
+	assert_eq!(account_id32_from_ss58(&bob_polkadot, Polkadot), Ok(junction));
+
+	assert_eq!(account_id32_to_ss58(&Parachain(1), 42), None);
+	assert_eq!(
+		account_id32_from_ss58("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQZ", Any),
+		Err(Ss58Error::BadChecksum),
+	);
+	assert!(matches!(account_id32_from_ss58("not an address", Any), Err(Ss58Error::Malformed(_))));
+}
+
+#[test]
+fn weight_info_bounds_should_use_per_instruction_weights() {
+	struct TestWeightInfo;
+	impl XcmWeightInfo for TestWeightInfo {
+		fn withdraw_asset() -> Weight { 100 }
+		fn reserve_asset_deposit() -> Weight { 200 }
+		fn teleport_asset() -> Weight { 300 }
+		fn query_response() -> Weight { 5 }
+		fn transfer_asset() -> Weight { 400 }
+		fn transfer_reserve_asset() -> Weight { 500 }
+		fn transact() -> Weight { 50 }
+		fn relayed_from() -> Weight { 7 }
+		fn deposit_asset() -> Weight { 10 }
+		fn deposit_reserve_asset() -> Weight { 20 }
+		fn exchange_asset() -> Weight { 30 }
+		fn initiate_reserve_withdraw() -> Weight { 40 }
+		fn initiate_teleport() -> Weight { 60 }
+		fn query_holding() -> Weight { 70 }
+		fn buy_execution() -> Weight { 80 }
+		fn fallback() -> Weight { 1_000 }
+	}
+	parameter_types! {
+		pub const MaxInstructions: u32 = 5;
+	}
+	type Weigher = WeightInfoBounds<TestWeightInfo, TestCall, MaxInstructions>;
+
+	// `Transact` costs its own weight on top of the call's; a response costs only its own.
+	let mut transact = Xcm::Transact {
+		origin_type: OriginKind::Native,
+		require_weight_at_most: 30,
+		call: TestCall::Any(30, None).encode().into(),
+	};
+	assert_eq!(Weigher::weight(&mut transact), Ok(80));
+	let mut response = Xcm::QueryResponse { query_id: 0, response: Response::Assets(vec![]) };
+	assert_eq!(Weigher::weight(&mut response), Ok(5));
+
+	// Orders are added to their instruction, and anything behind `BuyExecution` counts as deep weight.
+	let mut message = Xcm::RelayedFrom {
+		who: X1(Parachain(1)),
+		message: Box::new(Xcm::WithdrawAsset {
+			assets: vec![ All ],
+			effects: vec![
+				Order::BuyExecution { fees: All, weight: 0, debt: 0, halt_on_error: true, xcm: vec![ response ] },
+				Order::DepositAsset { assets: vec![ All ], dest: Null },
+			],
+		}),
+	};
+	assert_eq!(Weigher::shallow(&mut message), Ok(7 + 100 + 80 + 10));
+	assert_eq!(Weigher::deep(&mut message), Ok(5));
+	assert_eq!(Weigher::weight(&mut message), Ok(7 + 100 + 80 + 10 + 5));
+
+	// That was exactly `MaxInstructions`; one more is too many.
+	if let Xcm::RelayedFrom { message: ref mut inner, .. } = message {
+		if let Xcm::WithdrawAsset { ref mut effects, .. } = **inner {
+			effects.push(Order::Null);
+		}
+	}
+	// The limit applies to the message as a whole, so only `weight` checks it.
+	assert_eq!(Weigher::shallow(&mut message), Ok(7 + 100 + 80 + 10 + 1_000));
+	assert_eq!(Weigher::weight(&mut message), Err(()));
+
+	// The effects of `TransferReserveAsset` run at the destination, so neither their weight nor their number counts.
+	let mut transfer = Xcm::TransferReserveAsset {
+		assets: vec![ All ],
+		dest: X1(Parachain(2)),
+		effects: vec![ Order::Null; 10 ],
+	};
+	assert_eq!(Weigher::shallow(&mut transfer), Ok(500));
+}
+
+#[test]
+fn transactor_should_receive_message_context() {
+	AllowUnpaidFrom::set(vec![ X1(Parachain(1)) ]);
+	add_asset(1001, ConcreteFungible { id: Null, amount: 1000 });
+
+	let origin = X1(Parachain(1));
+	let message = Xcm::<TestCall>::WithdrawAsset {
+		assets: vec![ ConcreteFungible { id: Null, amount: 100 } ],
+		effects: vec![ Order::DepositAsset { assets: vec![ All ], dest: X1(AccountIndex64 { index: 3, network: Any }) } ],
+	};
+	let message_hash = xcm::VersionedXcm::from(message.clone()).hash();
+	let r = XcmExecutor::<TestConfig>::execute_xcm(origin.clone(), message, 50);
+	assert_eq!(r, Outcome::Complete(20));
+	assert_eq!(assets(3), vec![ ConcreteFungible { id: Null, amount: 100 } ]);
+
+	// Both the withdrawal and the deposit are attributed to the message and its origin.
+	let context = XcmContext { origin: Some(origin), message_hash, topic: None };
+	assert_eq!(transact_contexts(), vec![context.clone(), context]);
+}
+
+#[test]
+fn nested_message_should_carry_outer_message_hash() {
+	let who = X1(AccountIndex64 { index: 1, network: Any });
+	AllowUnpaidFrom::set(vec![ Null, who.clone() ]);
+	add_asset(1, ConcreteFungible { id: Null, amount: 1000 });
+
+	let beneficiary = X1(AccountIndex64 { index: 3, network: Any });
+	let message = Xcm::<TestCall>::RelayedFrom {
+		who: who.clone(),
+		message: Box::new(Xcm::WithdrawAsset {
+			assets: vec![ ConcreteFungible { id: Null, amount: 100 } ],
+			effects: vec![ Order::DepositAsset { assets: vec![ All ], dest: beneficiary } ],
+		}),
+	};
+	let message_hash = xcm::VersionedXcm::from(message.clone()).hash();
+	let r = XcmExecutor::<TestConfig>::execute_xcm(Null, message, 50);
+	assert_eq!(r.ensure_complete(), Ok(()));
+	assert_eq!(assets(3), vec![ ConcreteFungible { id: Null, amount: 100 } ]);
+
+	// The relayed message is not hashed on its own; its transactions are attributed to the message which carried it.
+	let context = XcmContext { origin: Some(who), message_hash, topic: None };
+	assert_eq!(transact_contexts(), vec![context.clone(), context]);
+}
+
+#[test]
+fn backing_fraction_should_reject_meaningless_backings() {
+	use frame_support::traits::Backing;
+	use xcm::v0::BodyPart;
+	use crate::origin_conversion::backing_fraction;
+
+	let fraction = |approvals, eligible| backing_fraction(Backing { approvals, eligible });
+	assert_eq!(fraction(3, 5), Some(BodyPart::Fraction { nom: 3, denom: 5 }));
+	assert_eq!(fraction(0, 5), Some(BodyPart::Fraction { nom: 0, denom: 5 }));
+	assert_eq!(fraction(5, 5), Some(BodyPart::Fraction { nom: 5, denom: 5 }));
+	assert_eq!(fraction(0, 0), None);
+	assert_eq!(fraction(1, 0), None);
+	assert_eq!(fraction(6, 5), None);
+
+	// The fraction is kept exact, so the majority threshold is neither rounded up nor down.
+	assert!(fraction(3, 5).unwrap().is_majority());
+	assert!(!fraction(2, 4).unwrap().is_majority());
+	assert!(fraction(u32::MAX / 2 + 1, u32::MAX).unwrap().is_majority());
+}
+
+#[test]
+fn origin_kind_filter_should_restrict_requested_kinds() {
+	use OriginKind::{Native, SovereignAccount, Superuser};
+
+	// Siblings may only act through their sovereign account; the parent may additionally be superuser.
+	struct SiblingsSovereignParentSuperuser;
+	impl Contains<(MultiLocation, OriginKind)> for SiblingsSovereignParentSuperuser {
+		fn contains(t: &(MultiLocation, OriginKind)) -> bool {
+			matches!(t,
+				(X2(Parent, Parachain(_)), SovereignAccount)
+				| (X1(Parent), SovereignAccount)
+				| (X1(Parent), Superuser)
+			)
+		}
+	}
+	type Converter = OriginKindFilter<TestOriginConverter, SiblingsSovereignParentSuperuser>;
+
+	let sibling = X2(Parent, Parachain(2));
+	assert_eq!(Converter::convert_origin(sibling.clone(), SovereignAccount), Ok(TestOrigin::Signed(2002)));
+	assert_eq!(Converter::convert_origin(sibling.clone(), Native), Err(sibling.clone()));
+	assert_eq!(Converter::convert_origin(sibling.clone(), Superuser), Err(sibling.clone()));
+	assert_eq!(Converter::convert_origin(sibling.clone(), OriginKind::Xcm), Err(sibling));
+
+	assert_eq!(Converter::convert_origin(X1(Parent), SovereignAccount), Ok(TestOrigin::Signed(3001)));
+	assert_eq!(Converter::convert_origin(X1(Parent), Superuser), Ok(TestOrigin::Root));
+	assert_eq!(Converter::convert_origin(X1(Parent), Native), Err(X1(Parent)));
+
+	// Anything the policy does not mention is rejected outright, even if the inner converter would accept it.
+	assert_eq!(TestOriginConverter::convert_origin(X1(Parachain(1)), Superuser), Ok(TestOrigin::Root));
+	assert_eq!(Converter::convert_origin(X1(Parachain(1)), Superuser), Err(X1(Parachain(1))));
+}
+
+#[test]
+fn is_child_system_parachain_should_use_configured_range() {
+	use polkadot_parachain::primitives::Id as ParaId;
+
+	// The default range follows `IsSystem`.
+	assert!(SystemParachains::contains(&ParaId::from(1)));
+	assert!(SystemParachains::contains(&ParaId::from(1999)));
+	assert!(!SystemParachains::contains(&ParaId::from(2000)));
+	assert!(IsChildSystemParachain::<SystemParachains>::contains(&X1(Parachain(1999))));
+	assert!(!IsChildSystemParachain::<SystemParachains>::contains(&X1(Parachain(2000))));
+
+	// A chain may instead treat only para 1 as a system chain.
+	struct OnlyParaOne;
+	impl Contains<ParaId> for OnlyParaOne {
+		fn contains(id: &ParaId) -> bool {
+			*id == ParaId::from(1)
+		}
+	}
+	type IsSystemChild = IsChildSystemParachain<OnlyParaOne>;
+	assert!(IsSystemChild::contains(&X1(Parachain(1))));
+	assert!(!IsSystemChild::contains(&X1(Parachain(2))));
+	// Only direct children qualify; siblings and nested locations do not.
+	assert!(!IsSystemChild::contains(&X2(Parent, Parachain(1))));
+	assert!(!IsSystemChild::contains(&X2(Parachain(1), Parachain(1))));
+
+	// As a barrier filter, para 1 gets free execution while para 2 does not.
+	let mut message = opaque::Xcm::Transact {
+		origin_type: OriginKind::Superuser,
+		require_weight_at_most: 30,
+		call: TestCall::Any(30, None).encode().into(),
+	};
+	let r = AllowUnpaidExecutionFrom::<IsSystemChild>::should_execute(
+		&X1(Parachain(1)), true, &mut message, 30, &mut 0,
+	);
+	assert_eq!(r, Ok(()));
+	let r = AllowUnpaidExecutionFrom::<IsSystemChild>::should_execute(
+		&X1(Parachain(2)), true, &mut message, 30, &mut 0,
+	);
+	assert_eq!(r, Err(()));
+}
+
+/// The converters between signed origins and `AccountKey20` locations need a real `OriginTrait` origin, so they are
+/// tested against a minimal runtime with 20-byte accounts.
+mod account_key20_origins {
+	use frame_support::{parameter_types, traits::OriginTrait};
+	use sp_core::{H160, H256};
+	use sp_runtime::{testing::Header, traits::{BlakeTwo256, IdentityLookup}};
+	use xcm::v0::{Junction::*, MultiLocation::{self, *}, NetworkId, OriginKind};
+	use xcm_executor::traits::{Convert, ConvertOrigin};
+	use crate::{SignedAccountKey20AsNative, SignedToAccountKey20};
+
+	type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+	type Block = frame_system::mocking::MockBlock<Test>;
+
+	frame_support::construct_runtime!(
+		pub enum Test where
+			Block = Block,
+			NodeBlock = Block,
+			UncheckedExtrinsic = UncheckedExtrinsic,
+		{
+			System: frame_system::{Pallet, Call, Storage, Event<T>},
+		}
+	);
+
+	parameter_types! {
+		pub const BlockHashCount: u64 = 250;
+		pub const KusamaNetwork: NetworkId = NetworkId::Kusama;
+		pub const PolkadotNetwork: NetworkId = NetworkId::Polkadot;
+	}
+
+	impl frame_system::Config for Test {
+		type BaseCallFilter = ();
+		type BlockWeights = ();
+		type BlockLength = ();
+		type DbWeight = ();
+		type Origin = Origin;
+		type Call = Call;
+		type Index = u64;
+		type BlockNumber = u64;
+		type Hash = H256;
+		type Hashing = BlakeTwo256;
+		type AccountId = H160;
+		type Lookup = IdentityLookup<H160>;
+		type Header = Header;
+		type Event = Event;
+		type BlockHashCount = BlockHashCount;
+		type Version = ();
+		type PalletInfo = PalletInfo;
+		type AccountData = ();
+		type OnNewAccount = ();
+		type OnKilledAccount = ();
+		type SystemWeightInfo = ();
+		type SS58Prefix = ();
+		type OnSetCode = ();
+	}
+
+	type ToLocation = SignedToAccountKey20<Origin, H160, KusamaNetwork>;
+	type ToOrigin = SignedAccountKey20AsNative<KusamaNetwork, Origin>;
+
+	const KEY: [u8; 20] = [7; 20];
+
+	fn caller_of(origin: Origin) -> OriginCaller {
+		origin.caller().clone()
+	}
+
+	#[test]
+	fn signed_origin_should_convert_to_account_key20() {
+		let location = ToLocation::convert(Origin::signed(KEY.into())).ok();
+		assert_eq!(location, Some(X1(AccountKey20 { network: NetworkId::Kusama, key: KEY })));
+
+		// Anything but a signed origin is handed back untouched.
+		for origin in vec![Origin::root(), Origin::none()] {
+			let expected = caller_of(origin.clone());
+			assert_eq!(ToLocation::convert(origin).err().map(caller_of), Some(expected));
+		}
+	}
+
+	#[test]
+	fn account_key20_should_convert_to_signed_origin() {
+		let signed = caller_of(Origin::signed(KEY.into()));
+		for network in vec![NetworkId::Kusama, NetworkId::Any] {
+			let location = X1(AccountKey20 { network, key: KEY });
+			let origin = ToOrigin::convert_origin(location, OriginKind::Native).ok().map(caller_of);
+			assert_eq!(origin, Some(signed.clone()));
+		}
+	}
+
+	#[test]
+	fn account_key20_should_be_rejected_from_other_networks_kinds_and_shapes() {
+		let rejected: Vec<(MultiLocation, OriginKind)> = vec![
+			(X1(AccountKey20 { network: NetworkId::Polkadot, key: KEY }), OriginKind::Native),
+			(X1(AccountKey20 { network: NetworkId::Kusama, key: KEY }), OriginKind::SovereignAccount),
+			(X1(AccountId32 { network: NetworkId::Kusama, id: [7; 32] }), OriginKind::Native),
+			(X2(Parent, AccountKey20 { network: NetworkId::Kusama, key: KEY }), OriginKind::Native),
+		];
+		for (location, kind) in rejected {
+			assert_eq!(ToOrigin::convert_origin(location.clone(), kind).err(), Some(location));
+		}
+	}
+
+	#[test]
+	fn account_key20_round_trip_should_only_succeed_on_the_same_network() {
+		let origin = Origin::signed(KEY.into());
+		let expected = caller_of(origin.clone());
+		let location = ToLocation::convert(origin).ok().expect("signed origins convert; qed");
+		let back = ToOrigin::convert_origin(location.clone(), OriginKind::Native).ok().map(caller_of);
+		assert_eq!(back, Some(expected));
+
+		// A chain on another network does not accept the location as one of its own accounts.
+		type PolkadotOrigin = SignedAccountKey20AsNative<PolkadotNetwork, Origin>;
+		assert_eq!(PolkadotOrigin::convert_origin(location.clone(), OriginKind::Native).err(), Some(location));
+	}
+}
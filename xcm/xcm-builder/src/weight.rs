@@ -20,53 +20,46 @@ use xcm::v0::{Xcm, Order, MultiAsset, MultiLocation, Error};
 use sp_runtime::traits::{Zero, Saturating, SaturatedConversion};
 use frame_support::traits::{Get, OnUnbalanced as OnUnbalancedT, tokens::currency::Currency as CurrencyT};
 use frame_support::weights::{Weight, GetDispatchInfo, WeightToFeePolynomial};
-use xcm_executor::{Assets, traits::{WeightBounds, WeightTrader}};
+use xcm_executor::{Assets, XcmContext, traits::{WeightBounds, WeightTrader}};
 
 pub struct FixedWeightBounds<T, C>(PhantomData<(T, C)>);
-impl<T: Get<Weight>, C: Decode + GetDispatchInfo> WeightBounds<C> for FixedWeightBounds<T, C> {
-	fn shallow(message: &mut Xcm<C>) -> Result<Weight, ()> {
+impl<T: Get<Weight>, C: Decode + GetDispatchInfo> FixedWeightBounds<T, C> {
+	/// `shallow`, given the fixed weight `unit` of a single instruction.
+	fn shallow_with(unit: Weight, message: &mut Xcm<C>) -> Result<Weight, ()> {
 		Ok(match message {
 			Xcm::Transact { call, .. } => {
-				call.ensure_decoded()?.get_dispatch_info().weight + T::get()
+				call.ensure_decoded()?.get_dispatch_info().weight + unit
 			}
-			Xcm::RelayedFrom { ref mut message, .. } => T::get() + Self::shallow(message.as_mut())?,
+			Xcm::RelayedFrom { ref mut message, .. } => unit + Self::shallow_with(unit, message.as_mut())?,
 			Xcm::WithdrawAsset { effects, .. }
 			| Xcm::ReserveAssetDeposit { effects, .. }
 			| Xcm::TeleportAsset { effects, .. }
 			=> {
-				let inner: Weight = effects.iter_mut()
-					.map(|effect| match effect {
-						Order::BuyExecution { .. } => {
-							// On success, execution of this will result in more weight being consumed but
-							// we don't count it here since this is only the *shallow*, non-negotiable weight
-							// spend and doesn't count weight placed behind a `BuyExecution` since it will not
-							// be definitely consumed from any existing weight credit if execution of the message
-							// is attempted.
-							T::get()
-						},
-						_ => T::get(),
-					}).sum();
-				T::get() + inner
+				// Every order costs the same `unit`. For `BuyExecution`, execution of the inner `xcm` will result
+				// in more weight being consumed but we don't count it here since this is only the *shallow*,
+				// non-negotiable weight spend and doesn't count weight placed behind a `BuyExecution` since it
+				// will not be definitely consumed from any existing weight credit if execution of the message is
+				// attempted.
+				unit * (effects.len() as Weight + 1)
 			}
-			_ => T::get(),
+			_ => unit,
 		})
 	}
-	fn deep(message: &mut Xcm<C>) -> Result<Weight, ()> {
+
+	/// `deep`, given the fixed weight `unit` of a single instruction.
+	fn deep_with(unit: Weight, message: &mut Xcm<C>) -> Result<Weight, ()> {
 		Ok(match message {
-			Xcm::RelayedFrom { ref mut message, .. } => Self::deep(message.as_mut())?,
+			Xcm::RelayedFrom { ref mut message, .. } => Self::deep_with(unit, message.as_mut())?,
 			Xcm::WithdrawAsset { effects, .. }
 			| Xcm::ReserveAssetDeposit { effects, .. }
 			| Xcm::TeleportAsset { effects, .. }
 			=> {
 				let mut extra = 0;
 				for effect in effects.iter_mut() {
-					match effect {
-						Order::BuyExecution { xcm, .. } => {
-							for message in xcm.iter_mut() {
-								extra += Self::shallow(message)? + Self::deep(message)?;
-							}
-						},
-						_ => {}
+					if let Order::BuyExecution { xcm, .. } = effect {
+						for message in xcm.iter_mut() {
+							extra += Self::shallow_with(unit, message)? + Self::deep_with(unit, message)?;
+						}
 					}
 				}
 				extra
@@ -75,31 +68,162 @@ impl<T: Get<Weight>, C: Decode + GetDispatchInfo> WeightBounds<C> for FixedWeigh
 		})
 	}
 }
+impl<T: Get<Weight>, C: Decode + GetDispatchInfo> WeightBounds<C> for FixedWeightBounds<T, C> {
+	fn shallow(message: &mut Xcm<C>) -> Result<Weight, ()> {
+		Self::shallow_with(T::get(), message)
+	}
+	fn deep(message: &mut Xcm<C>) -> Result<Weight, ()> {
+		Self::deep_with(T::get(), message)
+	}
+	fn weight(message: &mut Xcm<C>) -> Result<Weight, ()> {
+		let unit = T::get();
+		Self::shallow_with(unit, message)?.checked_add(Self::deep_with(unit, message)?).ok_or(())
+	}
+}
+
+/// Benchmarked weights of the individual XCM instructions and orders, for use with `WeightInfoBounds`.
+///
+/// Each function gives the weight of executing a single instruction or order, excluding anything nested in it
+/// (orders, the messages of `RelayedFrom` and `BuyExecution`) and, for `Transact`, the dispatch weight of the call.
+pub trait XcmWeightInfo {
+	fn withdraw_asset() -> Weight;
+	fn reserve_asset_deposit() -> Weight;
+	fn teleport_asset() -> Weight;
+	fn query_response() -> Weight;
+	fn transfer_asset() -> Weight;
+	fn transfer_reserve_asset() -> Weight;
+	fn transact() -> Weight;
+	fn relayed_from() -> Weight;
+	fn deposit_asset() -> Weight;
+	fn deposit_reserve_asset() -> Weight;
+	fn exchange_asset() -> Weight;
+	fn initiate_reserve_withdraw() -> Weight;
+	fn initiate_teleport() -> Weight;
+	fn query_holding() -> Weight;
+	fn buy_execution() -> Weight;
+	/// The weight of anything which has no benchmark of its own: the HRMP channel notifications and `Order::Null`.
+	/// The executor does not handle these, so this need only cover the cost of rejecting them.
+	fn fallback() -> Weight;
+}
+
+/// Weigher using per-instruction weights `W`, typically generated by benchmarking, instead of a fixed unit.
+///
+/// Messages with more than `M` instructions and orders in total, counting all those nested within, are rejected
+/// by `weight` as having no computable weight. The count is taken once for the whole message, so `shallow` and
+/// `deep`, which are also used on nested messages, do not check it.
+pub struct WeightInfoBounds<W, C, M>(PhantomData<(W, C, M)>);
+impl<W: XcmWeightInfo, C: Decode + GetDispatchInfo, M: Get<u32>> WeightInfoBounds<W, C, M> {
+	fn order_weight(order: &Order<C>) -> Weight {
+		match order {
+			Order::Null => W::fallback(),
+			Order::DepositAsset { .. } => W::deposit_asset(),
+			Order::DepositReserveAsset { .. } => W::deposit_reserve_asset(),
+			Order::ExchangeAsset { .. } => W::exchange_asset(),
+			Order::InitiateReserveWithdraw { .. } => W::initiate_reserve_withdraw(),
+			Order::InitiateTeleport { .. } => W::initiate_teleport(),
+			Order::QueryHolding { .. } => W::query_holding(),
+			// The inner `xcm` is counted in `deep`.
+			Order::BuyExecution { .. } => W::buy_execution(),
+		}
+	}
+
+	fn orders_weight(base: Weight, effects: &[Order<C>]) -> Result<Weight, ()> {
+		effects.iter().try_fold(base, |total, order| total.checked_add(Self::order_weight(order)).ok_or(()))
+	}
+
+	/// The number of instructions and orders in `message`, including those nested within it. As in `shallow`, the
+	/// effects of `TransferReserveAsset` are not counted since they are executed at the destination.
+	fn count_instructions(message: &Xcm<C>) -> u32 {
+		1 + match message {
+			Xcm::RelayedFrom { message, .. } => Self::count_instructions(message),
+			Xcm::WithdrawAsset { effects, .. }
+			| Xcm::ReserveAssetDeposit { effects, .. }
+			| Xcm::TeleportAsset { effects, .. }
+			=> effects.iter().fold(0u32, |count, effect| count.saturating_add(1).saturating_add(match effect {
+				Order::BuyExecution { xcm, .. } => xcm.iter()
+					.fold(0u32, |count, message| count.saturating_add(Self::count_instructions(message))),
+				_ => 0,
+			})),
+			_ => 0,
+		}
+	}
+}
+impl<W: XcmWeightInfo, C: Decode + GetDispatchInfo, M: Get<u32>> WeightBounds<C> for WeightInfoBounds<W, C, M> {
+	fn shallow(message: &mut Xcm<C>) -> Result<Weight, ()> {
+		match message {
+			Xcm::Transact { call, .. } =>
+				call.ensure_decoded()?.get_dispatch_info().weight.checked_add(W::transact()).ok_or(()),
+			Xcm::RelayedFrom { ref mut message, .. } =>
+				W::relayed_from().checked_add(Self::shallow(message.as_mut())?).ok_or(()),
+			Xcm::WithdrawAsset { effects, .. } => Self::orders_weight(W::withdraw_asset(), effects),
+			Xcm::ReserveAssetDeposit { effects, .. } => Self::orders_weight(W::reserve_asset_deposit(), effects),
+			Xcm::TeleportAsset { effects, .. } => Self::orders_weight(W::teleport_asset(), effects),
+			Xcm::QueryResponse { .. } => Ok(W::query_response()),
+			Xcm::TransferAsset { .. } => Ok(W::transfer_asset()),
+			// The effects are executed at the destination, not here.
+			Xcm::TransferReserveAsset { .. } => Ok(W::transfer_reserve_asset()),
+			Xcm::HrmpNewChannelOpenRequest { .. }
+			| Xcm::HrmpChannelAccepted { .. }
+			| Xcm::HrmpChannelClosing { .. }
+			=> Ok(W::fallback()),
+		}
+	}
+	fn deep(message: &mut Xcm<C>) -> Result<Weight, ()> {
+		match message {
+			Xcm::RelayedFrom { ref mut message, .. } => Self::deep(message.as_mut()),
+			Xcm::WithdrawAsset { effects, .. }
+			| Xcm::ReserveAssetDeposit { effects, .. }
+			| Xcm::TeleportAsset { effects, .. }
+			=> {
+				let mut extra: Weight = 0;
+				for effect in effects.iter_mut() {
+					if let Order::BuyExecution { xcm, .. } = effect {
+						for message in xcm.iter_mut() {
+							let weight = Self::shallow(message)?.checked_add(Self::deep(message)?).ok_or(())?;
+							extra = extra.checked_add(weight).ok_or(())?;
+						}
+					}
+				}
+				Ok(extra)
+			},
+			_ => Ok(0),
+		}
+	}
+	fn weight(message: &mut Xcm<C>) -> Result<Weight, ()> {
+		if Self::count_instructions(message) > M::get() {
+			return Err(());
+		}
+		Self::shallow(message)?.checked_add(Self::deep(message)?).ok_or(())
+	}
+}
 
 /// Function trait for handling some revenue. Similar to a negative imbalance (credit) handler, but for a
 /// `MultiAsset`. Sensible implementations will deposit the asset in some known treasury or block-author account.
 pub trait TakeRevenue {
-	/// Do something with the given `revenue`, which is a single non-wildcard `MultiAsset`.
-	fn take_revenue(revenue: MultiAsset);
+	/// Do something with the given `revenue`, which is a single non-wildcard `MultiAsset` paid by the message
+	/// executed in `context`.
+	fn take_revenue(revenue: MultiAsset, context: &XcmContext);
 }
 
 /// Null implementation just burns the revenue.
 impl TakeRevenue for () {
-	fn take_revenue(_revenue: MultiAsset) {}
+	fn take_revenue(_revenue: MultiAsset, _context: &XcmContext) {}
 }
 
 /// Simple fee calculator that requires payment in a single concrete fungible at a fixed rate.
 ///
 /// The constant `Get` type parameter should be the concrete fungible ID and the amount of it required for
 /// one second of weight.
+///
+/// The revenue is handed to `R` when the trader is dropped, along with the context in which weight was first bought.
 pub struct FixedRateOfConcreteFungible<
 	T: Get<(MultiLocation, u128)>,
 	R: TakeRevenue,
->(Weight, u128, PhantomData<(T, R)>);
+>(Weight, u128, Option<XcmContext>, PhantomData<(T, R)>);
 impl<T: Get<(MultiLocation, u128)>, R: TakeRevenue> WeightTrader for FixedRateOfConcreteFungible<T, R> {
-	fn new() -> Self { Self(0, 0, PhantomData) }
+	fn new() -> Self { Self(0, 0, None, PhantomData) }
 
-	fn buy_weight(&mut self, weight: Weight, payment: Assets) -> Result<Assets, Error> {
+	fn buy_weight(&mut self, weight: Weight, payment: Assets, context: &XcmContext) -> Result<Assets, Error> {
 		let (id, units_per_second) = T::get();
 		use frame_support::weights::constants::WEIGHT_PER_SECOND;
 		let amount = units_per_second * (weight as u128) / (WEIGHT_PER_SECOND as u128);
@@ -107,10 +231,11 @@ impl<T: Get<(MultiLocation, u128)>, R: TakeRevenue> WeightTrader for FixedRateOf
 		let (unused, _) = payment.less(required).map_err(|_| Error::TooExpensive)?;
 		self.0 = self.0.saturating_add(weight);
 		self.1 = self.1.saturating_add(amount);
+		self.2.get_or_insert_with(|| context.clone());
 		Ok(unused)
 	}
 
-	fn refund_weight(&mut self, weight: Weight) -> MultiAsset {
+	fn refund_weight(&mut self, weight: Weight, _context: &XcmContext) -> MultiAsset {
 		let (id, units_per_second) = T::get();
 		let weight = weight.min(self.0);
 		let amount = units_per_second * (weight as u128) / 1_000_000_000_000u128;
@@ -123,8 +248,11 @@ impl<T: Get<(MultiLocation, u128)>, R: TakeRevenue> WeightTrader for FixedRateOf
 
 impl<T: Get<(MultiLocation, u128)>, R: TakeRevenue> Drop for FixedRateOfConcreteFungible<T, R> {
 	fn drop(&mut self) {
-		let revenue = MultiAsset::ConcreteFungible { amount: self.1, id: T::get().0 };
-		R::take_revenue(revenue);
+		// Without a context, no weight was ever bought and there is no revenue.
+		if let Some(context) = self.2.take() {
+			let revenue = MultiAsset::ConcreteFungible { amount: self.1, id: T::get().0 };
+			R::take_revenue(revenue, &context);
+		}
 	}
 }
 
@@ -146,7 +274,7 @@ impl<
 > WeightTrader for UsingComponents<WeightToFee, AssetId, AccountId, Currency, OnUnbalanced> {
 	fn new() -> Self { Self(0, Zero::zero(), PhantomData) }
 
-	fn buy_weight(&mut self, weight: Weight, payment: Assets) -> Result<Assets, Error> {
+	fn buy_weight(&mut self, weight: Weight, payment: Assets, _context: &XcmContext) -> Result<Assets, Error> {
 		let amount = WeightToFee::calc(&weight);
 		let required = MultiAsset::ConcreteFungible {
 			amount: amount.try_into().map_err(|_| Error::Overflow)?,
@@ -158,7 +286,7 @@ impl<
 		Ok(unused)
 	}
 
-	fn refund_weight(&mut self, weight: Weight) -> MultiAsset {
+	fn refund_weight(&mut self, weight: Weight, _context: &XcmContext) -> MultiAsset {
 		let weight = weight.min(self.0);
 		let amount = WeightToFee::calc(&weight);
 		self.0 -= weight;
@@ -136,12 +136,15 @@ impl Assets {
 	/// Mutate `self` to contain all given `assets`, saturating if necessary.
 	///
 	/// Wildcards in `assets` are ignored.
-	pub fn saturating_subsume_all(&mut self, assets: Assets) {
-		// OPTIMIZE: Could be done with a much faster btree entry merge and only sum the entries with the
-		// same key.
-		for asset in assets.into_assets_iter() {
-			self.saturating_subsume(asset)
+	pub fn saturating_subsume_all(&mut self, mut assets: Assets) {
+		// Fold the smaller fungible map into the larger one, summing only the entries with the same key.
+		if assets.fungible.len() > self.fungible.len() {
+			mem::swap(&mut self.fungible, &mut assets.fungible);
 		}
+		for (id, amount) in assets.fungible.into_iter() {
+			self.saturating_subsume_fungible(id, amount);
+		}
+		self.non_fungible.append(&mut assets.non_fungible);
 	}
 
 	/// Mutate `self` to contain the given `asset`, saturating if necessary.
@@ -235,17 +238,26 @@ impl Assets {
 
 	pub fn take_non_fungible(&mut self, id: &AssetId) -> Assets {
 		let mut taken = Assets::new();
-		let non_fungible = mem::replace(&mut self.non_fungible, Default::default());
-		non_fungible.into_iter().for_each(|(c, instance)| {
-			if &c == id {
-				taken.non_fungible.insert((c, instance));
-			} else {
-				self.non_fungible.insert((c, instance));
-			}
-		});
+		let instances = self.non_fungible_of_class(id).cloned().collect::<Vec<_>>();
+		for class_instance in instances.into_iter() {
+			self.non_fungible.remove(&class_instance);
+			taken.non_fungible.insert(class_instance);
+		}
 		taken
 	}
 
+	/// An iterator over the non-fungible assets of `class`.
+	///
+	/// Entries are ordered by class first, so this visits only the matching range rather than every
+	/// non-fungible asset.
+	fn non_fungible_of_class<'a>(&'a self, class: &'a AssetId)
+		-> impl Iterator<Item=&'a (AssetId, AssetInstance)> + 'a
+	{
+		// `Undefined` is the least `AssetInstance`, so this is the first possible entry for `class`.
+		self.non_fungible.range((class.clone(), AssetInstance::Undefined)..)
+			.take_while(move |(c, _)| c == class)
+	}
+
 	pub fn try_remove_fungible(&mut self, id: &AssetId, amount: u128) -> Result<(), ()> {
 		let self_amount = self.fungible.get_mut(&id).ok_or(())?;
 		*self_amount = self_amount.checked_sub(amount).ok_or(())?;
@@ -340,45 +352,23 @@ impl Assets {
 						non_fungible: self.non_fungible.clone(),
 					}
 				},
-				MultiAsset::AllAbstractFungible { id } => {
-					for asset in self.fungible_assets_iter() {
-						match &asset {
-							MultiAsset::AbstractFungible { id: identifier, .. } => {
-								if id == identifier { result.saturating_subsume(asset) }
-							},
-							_ => (),
-						}
-					}
-				},
-				MultiAsset::AllAbstractNonFungible { class } => {
-					for asset in self.non_fungible_assets_iter() {
-						match &asset {
-							MultiAsset::AbstractNonFungible { class: c, .. } => {
-								if class == c { result.saturating_subsume(asset) }
-							},
-							_ => (),
-						}
-					}
-				}
-				MultiAsset::AllConcreteFungible { id } => {
-					for asset in self.fungible_assets_iter() {
-						match &asset {
-							MultiAsset::ConcreteFungible { id: identifier, .. } => {
-								if id == identifier { result.saturating_subsume(asset) }
-							},
-							_ => (),
-						}
+				x @ MultiAsset::AllAbstractFungible { .. } | x @ MultiAsset::AllConcreteFungible { .. } => {
+					let id = match x {
+						MultiAsset::AllConcreteFungible { id } => AssetId::Concrete(id.clone()),
+						MultiAsset::AllAbstractFungible { id } => AssetId::Abstract(id.clone()),
+						_ => unreachable!(),
+					};
+					if let Some(&amount) = self.fungible.get(&id) {
+						result.saturating_subsume_fungible(id, amount);
 					}
 				},
-				MultiAsset::AllConcreteNonFungible { class } => {
-					for asset in self.non_fungible_assets_iter() {
-						match &asset {
-							MultiAsset::ConcreteNonFungible { class: c, .. } => {
-								if class == c { result.saturating_subsume(asset) }
-							},
-							_ => (),
-						}
-					}
+				x @ MultiAsset::AllAbstractNonFungible { .. } | x @ MultiAsset::AllConcreteNonFungible { .. } => {
+					let class = match x {
+						MultiAsset::AllConcreteNonFungible { class } => AssetId::Concrete(class.clone()),
+						MultiAsset::AllAbstractNonFungible { class } => AssetId::Abstract(class.clone()),
+						_ => unreachable!(),
+					};
+					result.non_fungible.extend(self.non_fungible_of_class(&class).cloned());
 				}
 				x @ MultiAsset::ConcreteFungible { .. } | x @ MultiAsset::AbstractFungible { .. } => {
 					let (id, amount) = match x {
@@ -461,17 +451,9 @@ impl Assets {
 						MultiAsset::AllAbstractFungible { id } => AssetId::Abstract(id),
 						_ => unreachable!(),
 					};
-					// At the end of this block, we will be left with only the non-matching fungibles.
-					let mut non_matching_fungibles = BTreeMap::<AssetId, u128>::new();
-					let fungible = mem::replace(&mut self.fungible, Default::default());
-					fungible.into_iter().for_each(|(iden, amount)| {
-							if iden == id {
-								result.saturating_subsume_fungible(iden, amount);
-							} else {
-								non_matching_fungibles.insert(iden, amount);
-							}
-						});
-					self.fungible = non_matching_fungibles;
+					if let Some((id, amount)) = self.fungible.remove_entry(&id) {
+						result.saturating_subsume_fungible(id, amount);
+					}
 				},
 				x @ MultiAsset::AllAbstractNonFungible { .. } | x @ MultiAsset::AllConcreteNonFungible { .. } => {
 					let class = match x {
@@ -479,17 +461,8 @@ impl Assets {
 						MultiAsset::AllAbstractNonFungible { class } => AssetId::Abstract(class),
 						_ => unreachable!(),
 					};
-					// At the end of this block, we will be left with only the non-matching non-fungibles.
-					let mut non_matching_non_fungibles = BTreeSet::<(AssetId, AssetInstance)>::new();
-					let non_fungible = mem::replace(&mut self.non_fungible, Default::default());
-					non_fungible.into_iter().for_each(|(c, instance)| {
-							if class == c {
-								result.saturating_subsume_non_fungible(c, instance);
-							} else {
-								non_matching_non_fungibles.insert((c, instance));
-							}
-						});
-					self.non_fungible = non_matching_non_fungibles;
+					let mut taken = self.take_non_fungible(&class);
+					result.non_fungible.append(&mut taken.non_fungible);
 				},
 				x @ MultiAsset::ConcreteFungible {..} | x @ MultiAsset::AbstractFungible {..} => {
 					let (id, amount) = match x {
@@ -759,4 +732,53 @@ mod tests {
 		let assets = assets1.into_assets_iter().collect::<Vec<_>>();
 		assert_eq!(assets, vec![AF(1, 50), ANF(2, 200)]);
 	}
+
+	#[test]
+	fn saturating_subsume_all_merges() {
+		let mut assets = test_assets();
+		let mut other_vec: Vec<MultiAsset> = Vec::new();
+		other_vec.push(AF(1, 50));
+		other_vec.push(AF(3, 10));
+		other_vec.push(ANF(2, 200));
+		other_vec.push(ANF(2, 201));
+		other_vec.push(CF(u128::MAX));
+		assets.saturating_subsume_all(other_vec.into());
+
+		let assets = assets.into_assets_iter().collect::<Vec<_>>();
+		assert_eq!(assets, vec![
+			CF(u128::MAX), AF(1, 150), AF(3, 10), CNF(400), ANF(2, 200), ANF(2, 201),
+		]);
+	}
+
+	#[test]
+	fn wildcards_only_touch_matching_class() {
+		let mut assets_vec: Vec<MultiAsset> = Vec::new();
+		for class in 1..=3 {
+			assets_vec.push(AF(class, class as u128));
+			for instance in 0..3 {
+				assets_vec.push(ANF(class, instance));
+			}
+		}
+		assets_vec.push(MultiAsset::AbstractNonFungible { class: vec![2], instance: AssetInstance::Undefined });
+		let mut assets: Assets = assets_vec.into();
+
+		let wanted = vec![
+			MultiAsset::AllAbstractFungible { id: vec![2] },
+			MultiAsset::AllAbstractNonFungible { class: vec![2] },
+		];
+		let expected = vec![
+			AF(2, 2),
+			MultiAsset::AbstractNonFungible { class: vec![2], instance: AssetInstance::Undefined },
+			ANF(2, 0), ANF(2, 1), ANF(2, 2),
+		];
+		let min = assets.min(wanted.iter()).into_assets_iter().collect::<Vec<_>>();
+		assert_eq!(min, expected);
+		let taken = assets.saturating_take(wanted).into_assets_iter().collect::<Vec<_>>();
+		assert_eq!(taken, expected);
+
+		let left = assets.into_assets_iter().collect::<Vec<_>>();
+		assert_eq!(left, vec![
+			AF(1, 1), AF(3, 3), ANF(1, 0), ANF(1, 1), ANF(1, 2), ANF(3, 0), ANF(3, 1), ANF(3, 2),
+		]);
+	}
 }
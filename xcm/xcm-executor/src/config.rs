@@ -19,7 +19,7 @@ use frame_support::dispatch::{Dispatchable, Parameter};
 use frame_support::weights::{PostDispatchInfo, GetDispatchInfo};
 use crate::traits::{
 	TransactAsset, ConvertOrigin, FilterAssetLocation, InvertLocation, ShouldExecute, WeightTrader, WeightBounds,
-	OnResponse,
+	OnResponse, AssetExchange,
 };
 
 /// The trait to parametrize the `XcmExecutor`.
@@ -56,4 +56,7 @@ pub trait Config {
 
 	/// What to do when a response of a query is found.
 	type ResponseHandler: OnResponse;
+
+	/// The means of exchanging assets for `Order::ExchangeAsset`.
+	type AssetExchanger: AssetExchange;
 }
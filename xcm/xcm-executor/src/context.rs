@@ -0,0 +1,37 @@
+// Copyright 2021 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use xcm::{XcmHash, v0::MultiLocation};
+
+/// The context in which the executor calls into the asset transactor and weight trader.
+///
+/// This lets implementations attribute what they do, such as deposits or fees taken, to the message responsible.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct XcmContext {
+	/// The location on whose behalf the current instruction is being executed, if any.
+	pub origin: Option<MultiLocation>,
+	/// The `XcmHash` of the message being executed; for nested messages, that of the outermost one.
+	pub message_hash: XcmHash,
+	/// The topic of the message, if any. XCM v0 has no means of setting one, so this is currently always `None`.
+	pub topic: Option<[u8; 32]>,
+}
+
+impl XcmContext {
+	/// The context of executing the message with hash `message_hash` from `origin`, with no topic.
+	pub fn with_message_hash(origin: MultiLocation, message_hash: XcmHash) -> Self {
+		Self { origin: Some(origin), message_hash, topic: None }
+	}
+}
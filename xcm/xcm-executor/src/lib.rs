@@ -21,6 +21,7 @@ use frame_support::{
 	ensure, weights::GetDispatchInfo,
 	dispatch::{Weight, Dispatchable}
 };
+use xcm::{XcmHash, xcm_hash_v0};
 use xcm::v0::{
 	ExecuteXcm, SendXcm, Error as XcmError, Outcome,
 	MultiLocation, MultiAsset, Xcm, Order, Response,
@@ -29,13 +30,29 @@ use xcm::v0::{
 pub mod traits;
 use traits::{
 	TransactAsset, ConvertOrigin, FilterAssetLocation, InvertLocation, WeightBounds, WeightTrader, ShouldExecute,
-	OnResponse
+	OnResponse, AssetExchange,
 };
 
 mod assets;
 pub use assets::{Assets, AssetId};
 mod config;
 pub use config::Config;
+mod context;
+pub use context::XcmContext;
+
+/// Log targets used by the executor. They are stable so that they can be filtered on reliably.
+pub mod log_target {
+	/// Entry and exit of message execution, along with each instruction and order executed.
+	pub const EXECUTE: &str = "xcm::execute";
+	/// Messages rejected by the barrier.
+	pub const BARRIERS: &str = "xcm::barriers";
+	/// Calls into the asset transactor.
+	pub const TRANSACTOR: &str = "xcm::transactor";
+	/// Weight purchases and refunds.
+	pub const TRADER: &str = "xcm::trader";
+	/// Messages sent onwards.
+	pub const SEND: &str = "xcm::send";
+}
 
 pub struct XcmExecutor<Config>(PhantomData<Config>);
 
@@ -45,6 +62,24 @@ impl<Config: config::Config> ExecuteXcm<Config::Call> for XcmExecutor<Config> {
 		message: Xcm<Config::Call>,
 		weight_limit: Weight,
 		mut weight_credit: Weight,
+	) -> Outcome {
+		log::trace!(
+			target: log_target::EXECUTE,
+			"execute_xcm_in_credit origin: {:?}, message: {:?}, weight_limit: {:?}, weight_credit: {:?}",
+			origin, message, weight_limit, weight_credit,
+		);
+		let outcome = Self::execute_xcm_in_credit_inner(origin, message, weight_limit, weight_credit);
+		log::trace!(target: log_target::EXECUTE, "execute_xcm_in_credit outcome: {:?}", outcome);
+		outcome
+	}
+}
+
+impl<Config: config::Config> XcmExecutor<Config> {
+	fn execute_xcm_in_credit_inner(
+		origin: MultiLocation,
+		message: Xcm<Config::Call>,
+		weight_limit: Weight,
+		mut weight_credit: Weight,
 	) -> Outcome {
 		// TODO: #2841 #HARDENXCM We should identify recursive bombs here and bail.
 		let mut message = Xcm::<Config::Call>::from(message);
@@ -52,19 +87,24 @@ impl<Config: config::Config> ExecuteXcm<Config::Call> for XcmExecutor<Config> {
 			Ok(x) => x,
 			Err(()) => return Outcome::Error(XcmError::WeightNotComputable),
 		};
-		let deep_weight = match Config::Weigher::deep(&mut message) {
+		// `weight` rather than `shallow + deep` so that checks on the message as a whole are made once, here.
+		let maximum_weight = match Config::Weigher::weight(&mut message) {
 			Ok(x) => x,
 			Err(()) => return Outcome::Error(XcmError::WeightNotComputable),
 		};
-		let maximum_weight = match shallow_weight.checked_add(deep_weight) {
-			Some(x) => x,
-			None => return Outcome::Error(XcmError::Overflow),
-		};
 		if maximum_weight > weight_limit {
 			return Outcome::Error(XcmError::WeightLimitReached(maximum_weight));
 		}
 		let mut trader = Config::Trader::new();
-		let result = Self::do_execute_xcm(origin, true, message, &mut weight_credit, Some(shallow_weight), &mut trader);
+		let result = Self::do_execute_xcm(
+			origin,
+			true,
+			message,
+			None,
+			&mut weight_credit,
+			Some(shallow_weight),
+			&mut trader,
+		);
 		drop(trader);
 		match result {
 			Ok(surplus) => Outcome::Complete(maximum_weight.saturating_sub(surplus)),
@@ -73,22 +113,33 @@ impl<Config: config::Config> ExecuteXcm<Config::Call> for XcmExecutor<Config> {
 			Err(e) => Outcome::Incomplete(maximum_weight, e),
 		}
 	}
-}
 
-impl<Config: config::Config> XcmExecutor<Config> {
 	fn reanchored(mut assets: Assets, dest: &MultiLocation) -> Vec<MultiAsset> {
 		let inv_dest = Config::LocationInverter::invert_location(&dest);
 		assets.prepend_location(&inv_dest);
 		assets.into_assets_iter().collect::<Vec<_>>()
 	}
 
+	fn send_xcm(dest: MultiLocation, message: Xcm<()>) -> Result<(), XcmError> {
+		log::trace!(target: log_target::SEND, "send_xcm dest: {:?}, message: {:?}", dest, message);
+		let result = Config::XcmSender::send_xcm(dest, message);
+		if let Err(ref e) = result {
+			log::trace!(target: log_target::SEND, "send_xcm failed: {:?}", e);
+		}
+		result
+	}
+
 	/// Execute the XCM and return the portion of weight of `shallow_weight + deep_weight` that `message` did not use.
 	///
 	/// NOTE: The amount returned must be less than `shallow_weight + deep_weight` of `message`.
+	///
+	/// `message_hash` is `None` for a top-level message, which is hashed only once it has passed the barrier, so
+	/// that rejected messages cost no hashing. Nested messages are given the hash of the outermost one.
 	fn do_execute_xcm(
 		origin: MultiLocation,
 		top_level: bool,
 		mut message: Xcm<Config::Call>,
+		message_hash: Option<XcmHash>,
 		weight_credit: &mut Weight,
 		maybe_shallow_weight: Option<Weight>,
 		trader: &mut Config::Trader,
@@ -100,7 +151,21 @@ impl<Config: config::Config> XcmExecutor<Config> {
 			.ok_or(XcmError::WeightNotComputable)?;
 
 		Config::Barrier::should_execute(&origin, top_level, &message, shallow_weight, weight_credit)
-			.map_err(|()| XcmError::Barrier)?;
+			.map_err(|()| {
+				log::trace!(
+					target: log_target::BARRIERS,
+					"barrier rejected message origin: {:?}, top_level: {:?}, shallow_weight: {:?}, weight_credit: {:?}",
+					origin, top_level, shallow_weight, weight_credit,
+				);
+				XcmError::Barrier
+			})?;
+
+		let message_hash = message_hash.unwrap_or_else(|| xcm_hash_v0(&message));
+		log::trace!(
+			target: log_target::EXECUTE,
+			"do_execute_xcm origin: {:?}, hash: {:?}, message: {:?}", origin, message_hash, message,
+		);
+		let context = XcmContext::with_message_hash(origin.clone(), message_hash);
 
 		// The surplus weight, defined as the amount by which `shallow_weight` plus all nested
 		// `shallow_weight` values (ensuring no double-counting and also known as `deep_weight`) is an
@@ -113,7 +178,8 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				let mut holding = Assets::default();
 				for asset in assets {
 					ensure!(!asset.is_wildcard(), XcmError::Wildcard);
-					let withdrawn = Config::AssetTransactor::withdraw_asset(&asset, &origin)?;
+					log::debug!(target: log_target::TRANSACTOR, "withdraw_asset asset: {:?}, who: {:?}", asset, origin);
+					let withdrawn = Config::AssetTransactor::withdraw_asset(&asset, &origin, &context)?;
 					holding.saturating_subsume_all(withdrawn);
 				}
 				Some((holding, effects))
@@ -132,7 +198,12 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				// Take `assets` from the origin account (on-chain) and place into dest account.
 				for asset in assets {
 					ensure!(!asset.is_wildcard(), XcmError::Wildcard);
-					Config::AssetTransactor::teleport_asset(&asset, &origin, &dest)?;
+					log::debug!(
+						target: log_target::TRANSACTOR,
+						"teleport_asset asset: {:?}, from: {:?}, to: {:?}",
+						asset, origin, dest,
+					);
+					Config::AssetTransactor::teleport_asset(&asset, &origin, &dest, &context)?;
 				}
 				None
 			}
@@ -141,10 +212,15 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				let inv_dest = Config::LocationInverter::invert_location(&dest);
 				for asset in assets.iter_mut() {
 					ensure!(!asset.is_wildcard(), XcmError::Wildcard);
-					Config::AssetTransactor::teleport_asset(&asset, &origin, &dest)?;
+					log::debug!(
+						target: log_target::TRANSACTOR,
+						"teleport_asset asset: {:?}, from: {:?}, to: {:?}",
+						asset, origin, dest,
+					);
+					Config::AssetTransactor::teleport_asset(&asset, &origin, &dest, &context)?;
 					asset.reanchor(&inv_dest)?;
 				}
-				Config::XcmSender::send_xcm(dest, Xcm::ReserveAssetDeposit { assets, effects })?;
+				Self::send_xcm(dest, Xcm::ReserveAssetDeposit { assets, effects })?;
 				None
 			}
 			(origin, Xcm::TeleportAsset { assets, effects }) => {
@@ -157,10 +233,11 @@ impl<Config: config::Config> XcmExecutor<Config> {
 					// We should check that the asset can actually be teleported in (for this to be in error, there
 					// would need to be an accounting violation by one of the trusted chains, so it's unlikely, but we
 					// don't want to punish a possibly innocent chain/user).
-					Config::AssetTransactor::can_check_in(&origin, asset)?;
+					Config::AssetTransactor::can_check_in(&origin, asset, &context)?;
 				}
 				for asset in assets.iter() {
-					Config::AssetTransactor::check_in(&origin, asset);
+					log::debug!(target: log_target::TRANSACTOR, "check_in asset: {:?}, origin: {:?}", asset, origin);
+					Config::AssetTransactor::check_in(&origin, asset, &context);
 				}
 				Some((Assets::from(assets), effects))
 			}
@@ -202,7 +279,8 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				ensure!(who.is_interior(), XcmError::EscalationOfPrivilege);
 				let mut origin = origin;
 				origin.append_with(who).map_err(|_| XcmError::MultiLocationFull)?;
-				let surplus = Self::do_execute_xcm(origin, top_level, *message, weight_credit, None, trader)?;
+				let surplus =
+					Self::do_execute_xcm(origin, top_level, *message, Some(message_hash), weight_credit, None, trader)?;
 				total_surplus = total_surplus.saturating_add(surplus);
 				None
 			}
@@ -211,7 +289,10 @@ impl<Config: config::Config> XcmExecutor<Config> {
 
 		if let Some((mut holding, effects)) = maybe_holding_effects {
 			for effect in effects.into_iter() {
-				total_surplus += Self::execute_effects(&origin, &mut holding, effect, trader)?;
+				log::trace!(target: log_target::EXECUTE, "execute_effects effect: {:?}", effect);
+				let result = Self::execute_effects(&origin, &mut holding, effect, trader, &context);
+				log::trace!(target: log_target::EXECUTE, "execute_effects result: {:?}", result);
+				total_surplus += result?;
 			}
 		}
 
@@ -223,54 +304,84 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		holding: &mut Assets,
 		effect: Order<Config::Call>,
 		trader: &mut Config::Trader,
+		context: &XcmContext,
 	) -> Result<Weight, XcmError> {
 		let mut total_surplus = 0;
 		match effect {
 			Order::DepositAsset { assets, dest } => {
 				let deposited = holding.saturating_take(assets);
 				for asset in deposited.into_assets_iter() {
-					Config::AssetTransactor::deposit_asset(&asset, &dest)?;
+					log::debug!(target: log_target::TRANSACTOR, "deposit_asset asset: {:?}, who: {:?}", asset, dest);
+					Config::AssetTransactor::deposit_asset(&asset, &dest, context)?;
 				}
 			},
 			Order::DepositReserveAsset { assets, dest, effects } => {
 				let deposited = holding.saturating_take(assets);
 				for asset in deposited.assets_iter() {
-					Config::AssetTransactor::deposit_asset(&asset, &dest)?;
+					log::debug!(target: log_target::TRANSACTOR, "deposit_asset asset: {:?}, who: {:?}", asset, dest);
+					Config::AssetTransactor::deposit_asset(&asset, &dest, context)?;
 				}
 				let assets = Self::reanchored(deposited, &dest);
-				Config::XcmSender::send_xcm(dest, Xcm::ReserveAssetDeposit { assets, effects })?;
+				Self::send_xcm(dest, Xcm::ReserveAssetDeposit { assets, effects })?;
 			},
 			Order::InitiateReserveWithdraw { assets, reserve, effects} => {
 				let assets = Self::reanchored(holding.saturating_take(assets), &reserve);
-				Config::XcmSender::send_xcm(reserve, Xcm::WithdrawAsset { assets, effects })?;
+				Self::send_xcm(reserve, Xcm::WithdrawAsset { assets, effects })?;
 			}
 			Order::InitiateTeleport { assets, dest, effects} => {
 				for asset in assets.iter() {
-					Config::AssetTransactor::check_out(&origin, asset);
+					log::debug!(target: log_target::TRANSACTOR, "check_out asset: {:?}, dest: {:?}", asset, dest);
+					Config::AssetTransactor::check_out(&origin, asset, context);
 				}
 				let assets = Self::reanchored(holding.saturating_take(assets), &dest);
-				Config::XcmSender::send_xcm(dest, Xcm::TeleportAsset { assets, effects })?;
+				Self::send_xcm(dest, Xcm::TeleportAsset { assets, effects })?;
 			}
 			Order::QueryHolding { query_id, dest, assets } => {
 				let assets = Self::reanchored(holding.min(assets.iter()), &dest);
-				Config::XcmSender::send_xcm(dest, Xcm::QueryResponse { query_id, response: Response::Assets(assets) })?;
+				Self::send_xcm(dest, Xcm::QueryResponse { query_id, response: Response::Assets(assets) })?;
 			}
 			Order::BuyExecution { fees, weight, debt, halt_on_error, xcm } => {
 				// pay for `weight` using up to `fees` of the holding account.
 				let purchasing_weight = Weight::from(weight.checked_add(debt).ok_or(XcmError::Overflow)?);
 				let max_fee = holding.try_take(fees).map_err(|()| XcmError::NotHoldingFees)?;
-				let unspent = trader.buy_weight(purchasing_weight, max_fee)?;
+				log::trace!(target: log_target::TRADER, "buy_weight weight: {:?}, max_fee: {:?}", purchasing_weight, max_fee);
+				let unspent = trader.buy_weight(purchasing_weight, max_fee, context).map_err(|e| {
+					log::trace!(target: log_target::TRADER, "buy_weight failed: {:?}", e);
+					e
+				})?;
 				holding.saturating_subsume_all(unspent);
 
 				let mut remaining_weight = weight;
 				for message in xcm.into_iter() {
-					match Self::do_execute_xcm(origin.clone(), false, message, &mut remaining_weight, None, trader) {
+					let result = Self::do_execute_xcm(
+						origin.clone(),
+						false,
+						message,
+						Some(context.message_hash),
+						&mut remaining_weight,
+						None,
+						trader,
+					);
+					log::trace!(target: log_target::EXECUTE, "do_execute_xcm result: {:?}", result);
+					match result {
 						Err(e) if halt_on_error => return Err(e),
 						Err(_) => {}
 						Ok(surplus) => { total_surplus += surplus }
 					}
 				}
-				holding.saturating_subsume(trader.refund_weight(remaining_weight));
+				let refund = trader.refund_weight(remaining_weight, context);
+				log::trace!(target: log_target::TRADER, "refund_weight weight: {:?}, refund: {:?}", remaining_weight, refund);
+				holding.saturating_subsume(refund);
+			}
+			Order::ExchangeAsset { give, receive } => {
+				let give = holding.saturating_take(give);
+				match Config::AssetExchanger::exchange_asset(Some(origin), give, &receive) {
+					Ok(received) => holding.saturating_subsume_all(received),
+					Err(unspent) => {
+						holding.saturating_subsume_all(unspent);
+						return Err(XcmError::NoDeal);
+					}
+				}
 			}
 			_ => return Err(XcmError::UnhandledEffect)?,
 		}
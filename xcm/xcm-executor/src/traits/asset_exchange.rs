@@ -0,0 +1,45 @@
+// Copyright 2021 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use sp_std::result::Result;
+use xcm::v0::{MultiAsset, MultiLocation};
+use crate::Assets;
+
+/// A service for exchanging assets.
+pub trait AssetExchange {
+	/// Handler for exchanging an asset.
+	///
+	/// - `origin`: The location attempting the exchange; this should generally not matter.
+	/// - `give`: The assets which have been removed from the caller.
+	/// - `want`: The minimum amount of assets which should be given to the caller in case any exchange happens.
+	///
+	/// Returns `Ok` with the assets given in exchange, which must be at least `want`, or `Err` with the
+	/// untouched `give` if no exchange could be made.
+	fn exchange_asset(origin: Option<&MultiLocation>, give: Assets, want: &[MultiAsset]) -> Result<Assets, Assets>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl AssetExchange for Tuple {
+	fn exchange_asset(origin: Option<&MultiLocation>, give: Assets, want: &[MultiAsset]) -> Result<Assets, Assets> {
+		for_tuples!( #(
+			let give = match Tuple::exchange_asset(origin, give, want) {
+				Ok(r) => return Ok(r),
+				Err(a) => a,
+			};
+		)* );
+		Err(give)
+	}
+}
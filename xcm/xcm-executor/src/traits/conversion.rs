@@ -70,7 +70,7 @@ impl<A: Clone, B: Clone> Convert<A, B> for Tuple {
 	fn reverse_ref(value: impl Borrow<B>) -> Result<A, ()> {
 		let value = value.borrow();
 		for_tuples!( #(
-			match Tuple::reverse_ref(value.clone()) {
+			match Tuple::reverse_ref(value) {
 				Ok(result) => return Ok(result),
 				Err(_) => (),
 			}
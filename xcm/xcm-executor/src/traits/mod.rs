@@ -16,6 +16,8 @@
 
 //! Various traits used in configuring the executor.
 
+mod asset_exchange;
+pub use asset_exchange::AssetExchange;
 mod conversion;
 pub use conversion::{InvertLocation, ConvertOrigin, Convert, JustTry, Identity, Encoded, Decoded};
 mod filter_asset_location;
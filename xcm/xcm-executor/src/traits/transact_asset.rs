@@ -16,7 +16,7 @@
 
 use sp_std::result::Result;
 use xcm::v0::{Error as XcmError, Result as XcmResult, MultiAsset, MultiLocation};
-use crate::Assets;
+use crate::{Assets, XcmContext};
 
 /// Facility for asset transacting.
 ///
@@ -27,7 +27,7 @@ pub trait TransactAsset {
 	/// Ensure that `check_in` will result in `Ok`.
 	///
 	/// When composed as a tuple, all type-items are called and at least one must result in `Ok`.
-	fn can_check_in(_origin: &MultiLocation, _what: &MultiAsset) -> XcmResult {
+	fn can_check_in(_origin: &MultiLocation, _what: &MultiAsset, _context: &XcmContext) -> XcmResult {
 		Err(XcmError::Unimplemented)
 	}
 
@@ -44,7 +44,7 @@ pub trait TransactAsset {
 	///
 	/// When composed as a tuple, all type-items are called. It is up to the implementor that there exists no
 	/// value for `_what` which can cause side-effects for more than one of the type-items.
-	fn check_in(_origin: &MultiLocation, _what: &MultiAsset) {}
+	fn check_in(_origin: &MultiLocation, _what: &MultiAsset, _context: &XcmContext) {}
 
 	/// An asset has been teleported out to the given destination. This should do whatever housekeeping is needed.
 	///
@@ -56,12 +56,12 @@ pub trait TransactAsset {
 	///
 	/// When composed as a tuple, all type-items are called. It is up to the implementor that there exists no
 	/// value for `_what` which can cause side-effects for more than one of the type-items.
-	fn check_out(_origin: &MultiLocation, _what: &MultiAsset) {}
+	fn check_out(_origin: &MultiLocation, _what: &MultiAsset, _context: &XcmContext) {}
 
 	/// Deposit the `what` asset into the account of `who`.
 	///
 	/// Implementations should return `XcmError::FailedToTransactAsset` if deposit failed.
-	fn deposit_asset(_what: &MultiAsset, _who: &MultiLocation) -> XcmResult {
+	fn deposit_asset(_what: &MultiAsset, _who: &MultiLocation, _context: &XcmContext) -> XcmResult {
 		Err(XcmError::Unimplemented)
 	}
 
@@ -69,26 +69,40 @@ pub trait TransactAsset {
 	/// the case of `what` being a wildcard, this may be something more specific.
 	///
 	/// Implementations should return `XcmError::FailedToTransactAsset` if withdraw failed.
-	fn withdraw_asset(_what: &MultiAsset, _who: &MultiLocation) -> Result<Assets, XcmError> {
+	fn withdraw_asset(
+		_what: &MultiAsset,
+		_who: &MultiLocation,
+		_context: &XcmContext,
+	) -> Result<Assets, XcmError> {
 		Err(XcmError::Unimplemented)
 	}
 
 	/// Move an `asset` `from` one location in `to` another location.
 	///
 	/// Returns `XcmError::FailedToTransactAsset` if transfer failed.
-	fn transfer_asset(_asset: &MultiAsset, _from: &MultiLocation, _to: &MultiLocation) -> Result<Assets, XcmError> {
+	fn transfer_asset(
+		_asset: &MultiAsset,
+		_from: &MultiLocation,
+		_to: &MultiLocation,
+		_context: &XcmContext,
+	) -> Result<Assets, XcmError> {
 		Err(XcmError::Unimplemented)
 	}
 
 	/// Move an `asset` `from` one location in `to` another location.
 	///
 	/// Attempts to use `transfer_asset` and if not available then falls back to using a two-part withdraw/deposit.
-	fn teleport_asset(asset: &MultiAsset, from: &MultiLocation, to: &MultiLocation) -> Result<Assets, XcmError> {
-		match Self::transfer_asset(asset, from, to) {
+	fn teleport_asset(
+		asset: &MultiAsset,
+		from: &MultiLocation,
+		to: &MultiLocation,
+		context: &XcmContext,
+	) -> Result<Assets, XcmError> {
+		match Self::transfer_asset(asset, from, to, context) {
 			Err(XcmError::Unimplemented) => {
-				let assets = Self::withdraw_asset(asset, from)?;
+				let assets = Self::withdraw_asset(asset, from, context)?;
 				// Not a very forgiving attitude; once we implement roll-backs then it'll be nicer.
-				Self::deposit_asset(asset, to)?;
+				Self::deposit_asset(asset, to, context)?;
 				Ok(assets)
 			}
 			result => result
@@ -98,40 +112,45 @@ pub trait TransactAsset {
 
 #[impl_trait_for_tuples::impl_for_tuples(30)]
 impl TransactAsset for Tuple {
-	fn can_check_in(origin: &MultiLocation, what: &MultiAsset) -> XcmResult {
+	fn can_check_in(origin: &MultiLocation, what: &MultiAsset, context: &XcmContext) -> XcmResult {
 		for_tuples!( #(
-			match Tuple::can_check_in(origin, what) {
+			match Tuple::can_check_in(origin, what, context) {
 				Err(XcmError::AssetNotFound) => (),
 				r => return r,
 			}
 		)* );
 		Err(XcmError::AssetNotFound)
 	}
-	fn check_in(origin: &MultiLocation, what: &MultiAsset) {
+	fn check_in(origin: &MultiLocation, what: &MultiAsset, context: &XcmContext) {
 		for_tuples!( #(
-			Tuple::check_in(origin, what);
+			Tuple::check_in(origin, what, context);
 		)* );
 	}
-	fn check_out(dest: &MultiLocation, what: &MultiAsset) {
+	fn check_out(dest: &MultiLocation, what: &MultiAsset, context: &XcmContext) {
 		for_tuples!( #(
-			Tuple::check_out(dest, what);
+			Tuple::check_out(dest, what, context);
 		)* );
 	}
-	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> XcmResult {
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation, context: &XcmContext) -> XcmResult {
 		for_tuples!( #(
-			match Tuple::deposit_asset(what, who) { o @ Ok(_) => return o, _ => () }
+			match Tuple::deposit_asset(what, who, context) { o @ Ok(_) => return o, _ => () }
 		)* );
 		Err(XcmError::Unimplemented)
 	}
-	fn withdraw_asset(what: &MultiAsset, who: &MultiLocation) -> Result<Assets, XcmError> {
+	fn withdraw_asset(what: &MultiAsset, who: &MultiLocation, context: &XcmContext) -> Result<Assets, XcmError> {
 		for_tuples!( #(
-			match Tuple::withdraw_asset(what, who) { o @ Ok(_) => return o, _ => () }
+			match Tuple::withdraw_asset(what, who, context) { o @ Ok(_) => return o, _ => () }
 		)* );
 		Err(XcmError::Unimplemented)
 	}
-	fn transfer_asset(what: &MultiAsset, from: &MultiLocation, to: &MultiLocation) -> Result<Assets, XcmError> {
+	fn transfer_asset(
+		what: &MultiAsset,
+		from: &MultiLocation,
+		to: &MultiLocation,
+		context: &XcmContext,
+	) -> Result<Assets, XcmError> {
 		for_tuples!( #(
-			match Tuple::transfer_asset(what, from, to) { o @ Ok(_) => return o, _ => () }
+			match Tuple::transfer_asset(what, from, to, context) { o @ Ok(_) => return o, _ => () }
 		)* );
 		Err(XcmError::Unimplemented)
 	}
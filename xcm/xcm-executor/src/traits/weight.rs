@@ -17,7 +17,7 @@
 use sp_std::result::Result;
 use xcm::v0::{Xcm, MultiAsset, MultiLocation, Error};
 use frame_support::weights::Weight;
-use crate::Assets;
+use crate::{Assets, XcmContext};
 
 /// Determine the weight of an XCM message.
 pub trait WeightBounds<Call> {
@@ -45,6 +45,9 @@ pub trait WeightBounds<Call> {
 	fn deep(message: &mut Xcm<Call>) -> Result<Weight, ()>;
 
 	/// Return the total weight for executing `message`.
+	///
+	/// This is only called for the top-level message, so any limits on the message as a whole belong here
+	/// rather than in `shallow` or `deep`.
 	fn weight(message: &mut Xcm<Call>) -> Result<Weight, ()> {
 		Self::shallow(message)?.checked_add(Self::deep(message)?).ok_or(())
 	}
@@ -65,18 +68,18 @@ pub trait WeightTrader: Sized {
 	/// Purchase execution weight credit in return for up to a given `fee`. If less of the fee is required
 	/// then the surplus is returned. If the `fee` cannot be used to pay for the `weight`, then an error is
 	/// returned.
-	fn buy_weight(&mut self, weight: Weight, payment: Assets) -> Result<Assets, Error>;
+	fn buy_weight(&mut self, weight: Weight, payment: Assets, context: &XcmContext) -> Result<Assets, Error>;
 
 	/// Attempt a refund of `weight` into some asset. The caller does not guarantee that the weight was
 	/// purchased using `buy_weight`.
 	///
 	/// Default implementation refunds nothing.
-	fn refund_weight(&mut self, _weight: Weight) -> MultiAsset { MultiAsset::None }
+	fn refund_weight(&mut self, _weight: Weight, _context: &XcmContext) -> MultiAsset { MultiAsset::None }
 }
 
 impl WeightTrader for () {
 	fn new() -> Self { () }
-	fn buy_weight(&mut self, _: Weight, _: Assets) -> Result<Assets, Error> {
+	fn buy_weight(&mut self, _: Weight, _: Assets, _: &XcmContext) -> Result<Assets, Error> {
 		Err(Error::Unimplemented)
 	}
 }